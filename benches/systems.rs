@@ -0,0 +1,75 @@
+use bevy::prelude::*;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use endless_stream::game::level::component::LevelFloor;
+use endless_stream::game::movement::movement_systems;
+use endless_stream::game::movement::component::{Collidable, Movable, MoveStep};
+use endless_stream::game::movement::resource::StaticColliders;
+use endless_stream::game::simulation::SIMULATION_TICK_SECS;
+use endless_stream::game::sprite::component::Layered;
+use endless_stream::game::sprite::system::layering_system;
+
+const ENTITY_COUNTS: [usize; 3] = [100, 1000, 5000];
+// About as many trees as the default level has.
+const OBSTACLES: usize = 100;
+
+// A level floor, a row of trees and `movers` entities spread over the level, all heading towards the middle.
+fn scene(movers: usize) -> World {
+    let mut world = World::new();
+    world.insert_resource(FixedTime::new_from_secs(SIMULATION_TICK_SECS));
+    world.init_resource::<StaticColliders>();
+
+    world.spawn((
+        SpatialBundle::default(),
+        Sprite { custom_size: Some(Vec2::new(5000., 2000.)), ..default() },
+        LevelFloor,
+    ));
+
+    for i in 0..OBSTACLES {
+        let x = -2500. + 50. * i as f32;
+        world.spawn((
+            Transform::from_xyz(x, x.cos() * 500., 0.),
+            Collidable { size: Transform::from_scale(Vec3::new(16., 20., 1.)) },
+        ));
+    }
+
+    for i in 0..movers {
+        // A deterministic scatter over most of the level.
+        let position = Vec2::new((i * 7919 % 4800) as f32 - 2400., (i * 104_729 % 1800) as f32 - 900.);
+        world.spawn((
+            Transform::from_translation(position.extend(0.)),
+            Movable { velocity: 150., direction: -position },
+            MoveStep::default(),
+            Collidable { size: Transform::from_scale(Vec3::new(26., 20., 1.)) },
+            Layered,
+        ));
+    }
+
+    world
+}
+
+fn bench_systems<M, S: IntoSystemConfigs<M>>(criterion: &mut Criterion, name: &str, systems: impl Fn() -> S) {
+    let mut group = criterion.benchmark_group(name);
+
+    for movers in ENTITY_COUNTS {
+        let mut world = scene(movers);
+        let mut schedule = Schedule::new();
+        schedule.add_systems(systems());
+
+        group.bench_with_input(BenchmarkId::from_parameter(movers), &movers, |bencher, _| {
+            bencher.iter(|| schedule.run(&mut world));
+        });
+    }
+
+    group.finish();
+}
+
+fn movement(criterion: &mut Criterion) {
+    bench_systems(criterion, "movement", movement_systems);
+}
+
+fn layering(criterion: &mut Criterion) {
+    bench_systems(criterion, "layering_system", || layering_system);
+}
+
+criterion_group!(benches, movement, layering);
+criterion_main!(benches);
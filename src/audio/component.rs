@@ -0,0 +1,10 @@
+use bevy::prelude::*;
+
+use crate::audio::event::MusicTrack;
+
+#[derive(Component)]
+pub struct MusicPlayer(pub MusicTrack);
+
+// One-shot sound effect; despawned by bevy once it finishes playing.
+#[derive(Component)]
+pub struct SfxPlayer;
@@ -0,0 +1,25 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum Sfx {
+    Hit,
+    Pickup,
+    LevelUp,
+    Death,
+}
+
+#[derive(Deserialize, Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum MusicTrack {
+    Menu,
+    Gameplay,
+    Boss,
+}
+
+// Sent by gameplay systems; the audio module decides whether it's actually played (see `SfxLimiter`).
+#[derive(Event)]
+pub struct SfxEvent(pub Sfx);
+
+// Switches the background music; sending the track that's already playing does nothing.
+#[derive(Event)]
+pub struct PlayMusicEvent(pub MusicTrack);
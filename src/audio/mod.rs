@@ -0,0 +1,29 @@
+use bevy::prelude::*;
+
+use crate::AppState;
+use crate::loading::resource::GameAssets;
+use event::{PlayMusicEvent, SfxEvent};
+use resource::SfxLimiter;
+use system::*;
+
+pub mod component;
+pub mod event;
+pub mod resource;
+pub mod system;
+
+pub struct GameAudioPlugin;
+
+impl Plugin for GameAudioPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<SfxEvent>()
+            .add_event::<PlayMusicEvent>()
+            .init_resource::<SfxLimiter>()
+            .add_systems(OnEnter(AppState::MainMenu), play_menu_music)
+            .add_systems(Update, (
+                (hit_sfx_system, gameplay_music_system).run_if(in_state(AppState::GamePlaying)),
+                sfx_system,
+                music_system,
+                music_volume_system,
+            ).chain().run_if(resource_exists::<GameAssets>()));
+    }
+}
@@ -0,0 +1,97 @@
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+
+use crate::audio::event::{MusicTrack, Sfx};
+
+impl MusicTrack {
+    pub const ALL: [MusicTrack; 3] = [MusicTrack::Menu, MusicTrack::Gameplay, MusicTrack::Boss];
+
+    pub fn path(&self) -> &'static str {
+        match self {
+            MusicTrack::Menu => "audio/music/menu.ogg",
+            MusicTrack::Gameplay => "audio/music/gameplay.ogg",
+            MusicTrack::Boss => "audio/music/boss.ogg",
+        }
+    }
+}
+
+impl Sfx {
+    pub const ALL: [Sfx; 4] = [Sfx::Hit, Sfx::Pickup, Sfx::LevelUp, Sfx::Death];
+
+    pub fn path(&self) -> &'static str {
+        match self {
+            Sfx::Hit => "audio/sfx/hit.ogg",
+            Sfx::Pickup => "audio/sfx/pickup.ogg",
+            Sfx::LevelUp => "audio/sfx/level_up.ogg",
+            Sfx::Death => "audio/sfx/death.ogg",
+        }
+    }
+
+    // Shortest time between two plays of the same effect.
+    pub fn min_interval_secs(&self) -> f32 {
+        match self {
+            Sfx::Hit => 0.05,
+            Sfx::Pickup => 0.03,
+            Sfx::LevelUp | Sfx::Death => 0.1,
+        }
+    }
+}
+
+// Keeps a horde of simultaneous hits from turning into hundreds of overlapping sounds: each effect has a minimum
+// interval between plays, and there's a cap on how many effects can be playing at once.
+#[derive(Resource)]
+pub struct SfxLimiter {
+    pub max_voices: usize,
+    last_played: HashMap<Sfx, f32>,
+}
+
+impl Default for SfxLimiter {
+    fn default() -> Self {
+        SfxLimiter {
+            max_voices: 16,
+            last_played: HashMap::new(),
+        }
+    }
+}
+
+impl SfxLimiter {
+    // Returns whether the effect may play at `now_secs`, recording the play if so.
+    pub fn try_play(&mut self, sfx: Sfx, now_secs: f32, playing_voices: usize) -> bool {
+        if playing_voices >= self.max_voices {
+            return false;
+        }
+
+        if let Some(last_played) = self.last_played.get(&sfx) {
+            if now_secs - last_played < sfx.min_interval_secs() {
+                return false;
+            }
+        }
+
+        self.last_played.insert(sfx, now_secs);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repeated_effects_are_rate_limited() {
+        let mut limiter = SfxLimiter::default();
+
+        assert!(limiter.try_play(Sfx::Hit, 1., 0));
+        assert!(!limiter.try_play(Sfx::Hit, 1.01, 0));
+        // Other effects have their own interval.
+        assert!(limiter.try_play(Sfx::Pickup, 1.01, 0));
+        assert!(limiter.try_play(Sfx::Hit, 1.1, 0));
+    }
+
+    #[test]
+    fn voices_are_capped() {
+        let mut limiter = SfxLimiter::default();
+
+        assert!(!limiter.try_play(Sfx::Death, 0., limiter.max_voices));
+        assert!(limiter.try_play(Sfx::Death, 0., limiter.max_voices - 1));
+    }
+}
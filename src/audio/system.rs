@@ -0,0 +1,157 @@
+use bevy::asset::LoadState;
+use bevy::audio::{Volume, VolumeLevel};
+use bevy::prelude::*;
+
+use crate::audio::component::{MusicPlayer, SfxPlayer};
+use crate::audio::event::{MusicTrack, PlayMusicEvent, Sfx, SfxEvent};
+use crate::audio::resource::SfxLimiter;
+use crate::data::resource::GameData;
+use crate::game::enemy::component::{Enemy, EnemyKind};
+use crate::game::health::event::DamageEvent;
+use crate::game::level::SelectedLevel;
+use crate::loading::resource::GameAssets;
+use crate::settings::resource::Settings;
+
+pub fn play_menu_music(mut music_events: EventWriter<PlayMusicEvent>) {
+    music_events.send(PlayMusicEvent(MusicTrack::Menu));
+}
+
+// Boss music plays for as long as a boss is alive, the level's own music (or the regular gameplay music if it
+// has none) the rest of the run.
+pub fn gameplay_music_system(
+    game_data: Res<GameData>,
+    selected_level: Res<SelectedLevel>,
+    enemies: Query<&EnemyKind, With<Enemy>>,
+    mut music_events: EventWriter<PlayMusicEvent>,
+) {
+    let bosses: Vec<_> = game_data.enemies.iter()
+        .filter(|archetype| archetype.boss)
+        .map(|archetype| &archetype.id)
+        .collect();
+    let boss_alive = !bosses.is_empty() && enemies.iter().any(|kind| bosses.contains(&&kind.0));
+
+    let level_music = game_data.level(&selected_level.0).and_then(|level| level.music).unwrap_or(MusicTrack::Gameplay);
+
+    music_events.send(PlayMusicEvent(if boss_alive { MusicTrack::Boss } else { level_music }));
+}
+
+pub fn music_system(
+    mut commands: Commands,
+    mut music_events: EventReader<PlayMusicEvent>,
+    game_assets: Res<GameAssets>,
+    settings: Res<Settings>,
+    current_music: Query<(Entity, &MusicPlayer)>,
+) {
+    // Only the last request of the frame matters.
+    let Some(PlayMusicEvent(track)) = music_events.iter().last() else { return };
+
+    if current_music.iter().any(|(_, music_player)| music_player.0 == *track) {
+        return;
+    }
+
+    for (entity, _) in &current_music {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    commands.spawn((
+        AudioBundle {
+            source: game_assets.music[track].clone(),
+            settings: PlaybackSettings::LOOP.with_volume(relative_volume(settings.music_volume())),
+        },
+        MusicPlayer(*track),
+    ));
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn sfx_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut sfx_events: EventReader<SfxEvent>,
+    asset_server: Res<AssetServer>,
+    game_assets: Res<GameAssets>,
+    settings: Res<Settings>,
+    mut limiter: ResMut<SfxLimiter>,
+    playing: Query<(), With<SfxPlayer>>,
+) {
+    let mut voices = playing.iter().count();
+
+    for SfxEvent(sfx) in sfx_events.iter() {
+        let source = &game_assets.sfx[sfx];
+        // Sounds that failed to load would never finish playing, and so never despawn or free their voice.
+        if asset_server.get_load_state(source) == LoadState::Failed {
+            continue;
+        }
+
+        if !limiter.try_play(*sfx, time.elapsed_seconds(), voices) {
+            continue;
+        }
+
+        commands.spawn((
+            AudioBundle {
+                source: source.clone(),
+                settings: PlaybackSettings::DESPAWN.with_volume(relative_volume(settings.sfx_volume())),
+            },
+            SfxPlayer,
+        ));
+        voices += 1;
+    }
+}
+
+pub fn hit_sfx_system(mut damage_events: EventReader<DamageEvent>, mut sfx_events: EventWriter<SfxEvent>) {
+    // The limiter would drop all but one anyway.
+    if damage_events.iter().next().is_some() {
+        sfx_events.send(SfxEvent(Sfx::Hit));
+    }
+
+    damage_events.clear();
+}
+
+// Already playing music follows the volume settings; sound effects are short enough to pick them up on their next play.
+pub fn music_volume_system(settings: Res<Settings>, music: Query<&AudioSink, With<MusicPlayer>>) {
+    if !settings.is_changed() {
+        return;
+    }
+
+    for sink in &music {
+        sink.set_volume(settings.music_volume());
+    }
+}
+
+fn relative_volume(volume: f32) -> Volume {
+    Volume::Relative(VolumeLevel::new(volume))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn music_with_enemies(level_music: Option<MusicTrack>, kinds: &[&str]) -> Vec<MusicTrack> {
+        let mut game_data = GameData::default();
+        game_data.levels.iter_mut().for_each(|level| level.music = level_music);
+
+        let mut app = App::new();
+        app.add_event::<PlayMusicEvent>()
+            .insert_resource(game_data)
+            .init_resource::<SelectedLevel>()
+            .add_systems(Update, gameplay_music_system);
+        for kind in kinds {
+            app.world.spawn((Enemy, EnemyKind(kind.to_string())));
+        }
+        app.update();
+
+        let events = app.world.resource::<Events<PlayMusicEvent>>();
+        events.iter_current_update_events().map(|PlayMusicEvent(track)| *track).collect()
+    }
+
+    #[test]
+    fn boss_music_plays_while_a_boss_is_alive() {
+        assert_eq!(music_with_enemies(None, &["grunt", "brute"]), vec![MusicTrack::Gameplay]);
+        assert_eq!(music_with_enemies(None, &["grunt", "ogre"]), vec![MusicTrack::Boss]);
+    }
+
+    #[test]
+    fn levels_can_pick_their_own_music() {
+        assert_eq!(music_with_enemies(Some(MusicTrack::Menu), &["grunt"]), vec![MusicTrack::Menu]);
+        assert_eq!(music_with_enemies(Some(MusicTrack::Menu), &["ogre"]), vec![MusicTrack::Boss]);
+    }
+}
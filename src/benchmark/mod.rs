@@ -0,0 +1,173 @@
+use std::collections::BTreeMap;
+use std::f32::consts::TAU;
+use std::time::{Duration, Instant};
+
+use bevy::ecs::system::CommandQueue;
+use bevy::prelude::*;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use serde::Serialize;
+
+use crate::data::resource::GameData;
+use crate::game::enemy::component::Enemy;
+use crate::game::enemy::system::spawn_enemy_at;
+use crate::game::health::component::Invulnerable;
+use crate::game::level::DEFAULT_LEVEL;
+use crate::game::player::component::PlayerControlled;
+use crate::game::feedback::component::DamageNumber;
+use crate::game::pickup::component::Pickup;
+use crate::game::pool::resource::{EntityPool, PoolKind, PoolMetrics};
+use crate::game::spawn_tree_at;
+use crate::game::weapon::component::Projectile;
+use crate::game::weapon::system::spawn_projectile_at;
+use crate::harness::HeadlessHarness;
+use crate::loading::resource::GameAssets;
+use telemetry::{install_span_timing, reset_span_timings, span_timings, SystemTiming};
+
+pub mod telemetry;
+
+// The benchmark's projectiles fly for the whole run unless they hit something.
+const PROJECTILE_SPEED: f32 = 300.;
+const PROJECTILE_SIZE: Vec2 = Vec2::new(10., 3.);
+const PROJECTILE_LIFETIME: Duration = Duration::from_secs(24 * 60 * 60);
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq)]
+pub struct BenchmarkConfig {
+    pub enemies: u32,
+    pub projectiles: u32,
+    pub props: u32,
+    pub ticks: u64,
+    pub seed: u64,
+}
+
+#[derive(Serialize, Debug)]
+pub struct BenchmarkReport {
+    pub config: BenchmarkConfig,
+    pub frame_time: FrameTimeStats,
+    pub ticks_per_second: f64,
+    // Keyed by the span at the top of each measured system; empty if the timing subscriber couldn't be installed.
+    pub systems: BTreeMap<&'static str, SystemTiming>,
+    pub enemies_left: usize,
+    pub projectiles_left: usize,
+    // Keyed by pool name; covers the benchmark's own spawning too.
+    pub pools: BTreeMap<&'static str, PoolMetrics>,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+pub struct FrameTimeStats {
+    pub mean_ms: f64,
+    pub p50_ms: f64,
+    pub p95_ms: f64,
+    pub p99_ms: f64,
+    pub max_ms: f64,
+}
+
+impl FrameTimeStats {
+    fn new(mut frame_times: Vec<Duration>) -> Self {
+        frame_times.sort();
+        let ms = |duration: Duration| duration.as_secs_f64() * 1000.;
+        let percentile = |fraction: f64| {
+            let index = ((frame_times.len() as f64 * fraction).ceil() as usize).clamp(1, frame_times.len().max(1)) - 1;
+            frame_times.get(index).copied().map_or(0., ms)
+        };
+
+        FrameTimeStats {
+            mean_ms: ms(frame_times.iter().sum::<Duration>()) / frame_times.len().max(1) as f64,
+            p50_ms: percentile(0.5),
+            p95_ms: percentile(0.95),
+            p99_ms: percentile(0.99),
+            max_ms: frame_times.last().copied().map_or(0., ms),
+        }
+    }
+}
+
+// Starts a headless run, crowds the level with the configured number of enemies, projectiles and props,
+// and times `config.ticks` ticks of it. Each tick is one frame, so frame times are tick times.
+pub fn run_benchmark(config: BenchmarkConfig) -> BenchmarkReport {
+    install_span_timing();
+
+    let mut harness = HeadlessHarness::new();
+    harness.start_run("gabe", config.seed);
+    spawn_benchmark_scene(&mut harness.app.world, config);
+    // Spawning takes a frame to settle; it shouldn't count towards the first tick.
+    harness.advance_ticks(1);
+    reset_span_timings();
+
+    let mut frame_times = Vec::with_capacity(config.ticks as usize);
+    let started = Instant::now();
+    for _ in 0..config.ticks {
+        let frame_started = Instant::now();
+        harness.advance_ticks(1);
+        frame_times.push(frame_started.elapsed());
+    }
+    let elapsed = started.elapsed();
+
+    BenchmarkReport {
+        config,
+        frame_time: FrameTimeStats::new(frame_times),
+        ticks_per_second: config.ticks as f64 / elapsed.as_secs_f64().max(f64::EPSILON),
+        systems: span_timings(),
+        enemies_left: harness.translations::<Enemy>().len(),
+        projectiles_left: harness.translations::<Projectile>().len(),
+        pools: BTreeMap::from([
+            pool_metrics::<Enemy>(&harness.app.world),
+            pool_metrics::<Projectile>(&harness.app.world),
+            pool_metrics::<Pickup>(&harness.app.world),
+            pool_metrics::<DamageNumber>(&harness.app.world),
+        ]),
+    }
+}
+
+fn pool_metrics<K: PoolKind>(world: &World) -> (&'static str, PoolMetrics) {
+    (K::NAME, world.resource::<EntityPool<K>>().metrics)
+}
+
+fn spawn_benchmark_scene(world: &mut World, config: BenchmarkConfig) {
+    let mut rng = ChaCha8Rng::seed_from_u64(config.seed);
+    let game_data = world.resource::<GameData>().clone();
+    let level = game_data.level(DEFAULT_LEVEL).expect("the default level is always defined");
+    let half_size = level.floor_size / 2. - PROJECTILE_SPEED;
+    let player = world.query_filtered::<Entity, With<PlayerControlled>>().single(world);
+    let mut enemy_pool = world.remove_resource::<EntityPool<Enemy>>().expect("no enemy pool");
+    let mut projectile_pool = world.remove_resource::<EntityPool<Projectile>>().expect("no projectile pool");
+
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, world);
+    let game_assets = world.resource::<GameAssets>();
+
+    // Every archetype but the bosses takes its turn, so the mix of sizes and speeds matches a real run.
+    let archetypes = game_data.enemies.iter().filter(|archetype| !archetype.boss);
+    for archetype in archetypes.cycle().take(config.enemies as usize) {
+        let position = random_position(&mut rng, half_size);
+        spawn_enemy_at(&mut commands, game_assets, &mut enemy_pool, archetype, position.extend(0.));
+    }
+
+    for _ in 0..config.projectiles {
+        let position = random_position(&mut rng, half_size);
+        let direction = Vec2::from_angle(rng.gen_range(0. ..TAU));
+        spawn_projectile_at(&mut commands, &mut projectile_pool, position.extend(1.), Projectile {
+            damage: 1.,
+            velocity: direction * PROJECTILE_SPEED,
+            size: PROJECTILE_SIZE,
+            lifetime: Timer::new(PROJECTILE_LIFETIME, TimerMode::Once),
+            knockback: 0.,
+            status_effect: None,
+        }, ());
+    }
+
+    for _ in 0..config.props {
+        let position = random_position(&mut rng, half_size);
+        spawn_tree_at(&mut commands, game_assets, position.extend(0.5 - position.y / 2000.));
+    }
+
+    // Enemies never stop chasing the player, so the player never stops being hit either; keep them around.
+    commands.entity(player).insert(Invulnerable);
+
+    queue.apply(world);
+    world.insert_resource(enemy_pool);
+    world.insert_resource(projectile_pool);
+}
+
+fn random_position(rng: &mut ChaCha8Rng, half_size: Vec2) -> Vec2 {
+    Vec2::new(rng.gen_range(-half_size.x..half_size.x), rng.gen_range(-half_size.y..half_size.y))
+}
@@ -0,0 +1,71 @@
+use std::collections::BTreeMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use bevy::utils::tracing::{span, subscriber, Subscriber};
+use serde::Serialize;
+use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+use tracing_subscriber::registry::{LookupSpan, Registry};
+
+static SPAN_TIMES: Mutex<BTreeMap<&'static str, SpanTime>> = Mutex::new(BTreeMap::new());
+
+#[derive(Clone, Copy, Debug, Default)]
+struct SpanTime {
+    calls: u64,
+    total: Duration,
+}
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq)]
+pub struct SystemTiming {
+    pub calls: u64,
+    pub total_ms: f64,
+    pub mean_ms: f64,
+}
+
+// Adds up how long the game's own spans (`info_span!` at the top of the systems worth measuring) were entered,
+// wherever in the task pool they ran.
+struct SpanTimingLayer;
+
+impl<S: Subscriber + for<'a> LookupSpan<'a>> Layer<S> for SpanTimingLayer {
+    fn on_enter(&self, id: &span::Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else { return };
+        if span.metadata().target().starts_with(env!("CARGO_CRATE_NAME")) {
+            span.extensions_mut().insert(Instant::now());
+        }
+    }
+
+    fn on_exit(&self, id: &span::Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else { return };
+        let Some(entered) = span.extensions_mut().remove::<Instant>() else { return };
+
+        let mut span_times = SPAN_TIMES.lock().unwrap();
+        let span_time = span_times.entry(span.name()).or_default();
+        span_time.calls += 1;
+        span_time.total += entered.elapsed();
+    }
+}
+
+// Makes span timing the process' tracing subscriber, returning whether it is. That only works if nothing,
+// such as Bevy's `LogPlugin`, got there first.
+pub fn install_span_timing() -> bool {
+    static INSTALLED: OnceLock<bool> = OnceLock::new();
+
+    *INSTALLED.get_or_init(|| subscriber::set_global_default(Registry::default().with(SpanTimingLayer)).is_ok())
+}
+
+pub fn reset_span_timings() {
+    SPAN_TIMES.lock().unwrap().clear();
+}
+
+pub fn span_timings() -> BTreeMap<&'static str, SystemTiming> {
+    SPAN_TIMES.lock().unwrap().iter()
+        .map(|(name, span_time)| {
+            let total_ms = span_time.total.as_secs_f64() * 1000.;
+            (*name, SystemTiming {
+                calls: span_time.calls,
+                total_ms,
+                mean_ms: total_ms / span_time.calls.max(1) as f64,
+            })
+        })
+        .collect()
+}
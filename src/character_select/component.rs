@@ -0,0 +1,5 @@
+use bevy::prelude::*;
+
+// Holds the id of the character in `GameData`.
+#[derive(Component)]
+pub struct CharacterSelectButton(pub String);
@@ -0,0 +1,53 @@
+use bevy::prelude::*;
+
+use crate::AppState;
+use crate::data::resource::GameData;
+use crate::menu::{spawn_menu_button, MenuState};
+use crate::menu::component::MenuButtonAction;
+use component::CharacterSelectButton;
+use resource::CharacterSelectScreen;
+use system::*;
+
+pub mod component;
+pub mod resource;
+pub mod system;
+
+pub struct CharacterSelectPlugin;
+
+impl Plugin for CharacterSelectPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(MenuState::CharacterSelect), setup_character_select)
+            .add_systems(Update, (character_select_system, character_select_text_system).chain()
+                .run_if(in_state(AppState::MainMenu).and_then(in_state(MenuState::CharacterSelect))))
+            .add_systems(OnExit(MenuState::CharacterSelect), cleanup_character_select);
+    }
+}
+
+fn setup_character_select(mut commands: Commands, game_data: Res<GameData>) {
+    let root = commands
+        .spawn(NodeBundle {
+            style: Style {
+                width: Val::Percent(100.),
+                height: Val::Percent(100.),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            ..default()
+        }).with_children(|parent| {
+            for character in &game_data.characters {
+                // Labels depend on what's unlocked, so they're filled in by `character_select_text_system`.
+                spawn_menu_button(parent, "", CharacterSelectButton(character.id.clone()));
+            }
+
+            spawn_menu_button(parent, "Back", MenuButtonAction::BackToMainMenu);
+        })
+        .id();
+
+    commands.insert_resource(CharacterSelectScreen { root });
+}
+
+fn cleanup_character_select(mut commands: Commands, screen: Res<CharacterSelectScreen>) {
+    commands.entity(screen.root).despawn_recursive();
+}
@@ -0,0 +1,72 @@
+use bevy::prelude::*;
+
+use crate::AppState;
+use crate::character_select::component::CharacterSelectButton;
+use crate::data::resource::GameData;
+use crate::game::character::SelectedCharacter;
+use crate::menu::MenuState;
+use crate::save::file::write_save;
+use crate::save::resource::{SaveData, SavePath};
+
+// Starts a run with an unlocked character, or unlocks a locked one if the player can afford it.
+#[allow(clippy::type_complexity)]
+pub fn character_select_system(
+    interaction_query: Query<(&Interaction, &CharacterSelectButton), (Changed<Interaction>, With<Button>)>,
+    game_data: Res<GameData>,
+    save_path: Res<SavePath>,
+    mut save: ResMut<SaveData>,
+    mut selected_character: ResMut<SelectedCharacter>,
+    mut game_state: ResMut<NextState<AppState>>,
+    mut menu_state: ResMut<NextState<MenuState>>,
+) {
+    for (interaction, CharacterSelectButton(id)) in &interaction_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        let Some(character) = game_data.character(id) else { continue };
+
+        if is_unlocked(&save, &character.id, character.unlock_cost) {
+            selected_character.0 = character.id.clone();
+            game_state.set(AppState::GamePlaying);
+            menu_state.set(MenuState::Disabled);
+        } else if save.meta.gold >= character.unlock_cost {
+            save.meta.gold -= character.unlock_cost;
+            save.meta.unlocked_characters.push(character.id.clone());
+
+            if let Err(error) = write_save(&save_path.0, &save) {
+                error!("Failed to write save after unlocking {}: {}", character.id, error);
+            }
+        }
+    }
+}
+
+pub fn character_select_text_system(
+    game_data: Res<GameData>,
+    save: Res<SaveData>,
+    buttons: Query<(&CharacterSelectButton, &Children)>,
+    added_buttons: Query<(), Added<CharacterSelectButton>>,
+    mut texts: Query<&mut Text>,
+) {
+    if !save.is_changed() && added_buttons.is_empty() {
+        return;
+    }
+
+    for (CharacterSelectButton(id), children) in &buttons {
+        let Some(character) = game_data.character(id) else { continue };
+        let label = if is_unlocked(&save, &character.id, character.unlock_cost) {
+            format!("{} - {} HP, {}", character.name, character.max_health, character.starting_weapon.name())
+        } else {
+            format!("{} - unlock for {}g", character.name, character.unlock_cost)
+        };
+
+        for child in children.iter() {
+            if let Ok(mut text) = texts.get_mut(*child) {
+                text.sections[0].value = label.clone();
+            }
+        }
+    }
+}
+
+fn is_unlocked(save: &SaveData, id: &str, unlock_cost: u32) -> bool {
+    unlock_cost == 0 || save.meta.unlocked_characters.iter().any(|unlocked| unlocked == id)
+}
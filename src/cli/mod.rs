@@ -0,0 +1,243 @@
+use std::path::PathBuf;
+
+use bevy::app::AppExit;
+use bevy::prelude::*;
+use bevy::window::WindowResolution;
+use clap::{ArgGroup, Parser};
+
+use crate::AppState;
+use crate::benchmark::BenchmarkConfig;
+use crate::data::resource::GameData;
+use crate::game::character::SelectedCharacter;
+use crate::game::health::component::Health;
+use crate::game::level::SelectedLevel;
+use crate::game::player::component::PlayerControlled;
+use crate::game::simulation::resource::{RunSeed, SimulationTick};
+use crate::loading::resource::LoadingTarget;
+use crate::menu::MenuState;
+use crate::replay::event::StartReplayEvent;
+use crate::replay::system::save_recording_on_exit;
+
+const DEFAULT_BENCHMARK_ENEMIES: u32 = 1000;
+
+// Launch options for jumping straight into a scenario, e.g. `--skip-menu --character dorian --seed 42`,
+// or for running one in CI with `--headless --ticks 3600`.
+#[derive(Parser, Debug, Default, PartialEq)]
+#[command(version, about = "A Vampire Survivors-esque game")]
+#[command(group(ArgGroup::new("windowless").args(["headless", "benchmark"])))]
+pub struct LaunchArgs {
+    #[arg(long, value_parser = parse_level, help = "Level to play")]
+    pub level: Option<String>,
+
+    #[arg(long, help = "Seed for the first run; random if not given")]
+    pub seed: Option<u64>,
+
+    #[arg(long, value_parser = parse_character, help = "Character to play, even if it isn't unlocked yet")]
+    pub character: Option<String>,
+
+    #[arg(long, help = "Start the run right away instead of showing the main menu")]
+    pub skip_menu: bool,
+
+    #[arg(
+        long,
+        value_name = "FILE",
+        conflicts_with_all = ["level", "seed", "character", "skip_menu"],
+        help = "Play back a replay file; it decides the level, seed and character",
+    )]
+    pub replay: Option<PathBuf>,
+
+    #[arg(long, requires = "ticks", help = "Run without a window, GPU or audio; implies --skip-menu")]
+    pub headless: bool,
+
+    #[arg(long, value_name = "N", requires = "windowless", help = "Number of simulation ticks to run headless before exiting")]
+    pub ticks: Option<u64>,
+
+    #[arg(
+        long,
+        requires = "ticks",
+        conflicts_with_all = ["level", "character", "skip_menu", "replay"],
+        help = "Time a headless run crowded with enemies, projectiles and props, and print the results as JSON",
+    )]
+    pub benchmark: bool,
+
+    #[arg(long, value_name = "N", requires = "benchmark", conflicts_with = "headless", help = "Enemies in the benchmark [default: 1000]")]
+    pub enemies: Option<u32>,
+
+    #[arg(long, value_name = "N", requires = "benchmark", conflicts_with = "headless", help = "Projectiles in the benchmark [default: 0]")]
+    pub projectiles: Option<u32>,
+
+    #[arg(long, value_name = "N", requires = "benchmark", conflicts_with = "headless", help = "Props in the benchmark, on top of the level's own [default: 0]")]
+    pub props: Option<u32>,
+
+    #[arg(long, value_name = "WxH", value_parser = parse_resolution, conflicts_with = "windowless", help = "Window size, e.g. 1280x720")]
+    pub windowed: Option<Vec2>,
+}
+
+impl LaunchArgs {
+    pub fn window(&self) -> Window {
+        let mut window = Window::default();
+        if let Some(size) = self.windowed {
+            window.resolution = WindowResolution::new(size.x, size.y);
+        }
+
+        window
+    }
+
+    pub fn benchmark_config(&self) -> Option<BenchmarkConfig> {
+        self.benchmark.then(|| BenchmarkConfig {
+            enemies: self.enemies.unwrap_or(DEFAULT_BENCHMARK_ENEMIES),
+            projectiles: self.projectiles.unwrap_or_default(),
+            props: self.props.unwrap_or_default(),
+            ticks: self.ticks.unwrap_or_default(),
+            seed: self.seed.unwrap_or_default(),
+        })
+    }
+
+    // Applies the options to an app that already has the game's plugins added.
+    pub fn configure(&self, app: &mut App) {
+        if let Some(level) = self.level.clone() {
+            app.insert_resource(SelectedLevel(level));
+        }
+
+        if let Some(seed) = self.seed {
+            app.insert_resource(RunSeed(seed));
+        }
+
+        if let Some(character) = self.character.clone() {
+            app.insert_resource(SelectedCharacter(character));
+        }
+
+        // Developers launching with options don't need the splash screen, but assets still have to be loaded first.
+        if self.replay.is_some() || self.skip_menu || self.headless {
+            app.insert_resource(NextState(Some(AppState::Loading)));
+        }
+
+        if let Some(path) = self.replay.clone() {
+            // Replays are started from the main menu, same as the menu's replay button.
+            app.add_systems(OnEnter(AppState::MainMenu), (move |mut start_events: EventWriter<StartReplayEvent>| {
+                start_events.send(StartReplayEvent(path.clone()));
+            }).run_if(run_once()));
+        } else if self.skip_menu || self.headless {
+            app.insert_resource(LoadingTarget {
+                app_state: AppState::GamePlaying,
+                menu_state: MenuState::Disabled,
+            });
+        }
+
+        if let Some(ticks) = self.ticks {
+            app.insert_resource(TickLimit(ticks))
+                .add_systems(Last, exit_after_tick_limit.before(save_recording_on_exit));
+        }
+    }
+}
+
+#[derive(Resource)]
+struct TickLimit(u64);
+
+fn exit_after_tick_limit(
+    tick_limit: Res<TickLimit>,
+    tick: Res<SimulationTick>,
+    state: Res<State<AppState>>,
+    player: Query<(&Transform, &Health), With<PlayerControlled>>,
+    mut exit_events: EventWriter<AppExit>,
+) {
+    // A replay that runs out before the limit ends the run early.
+    let run_ended = tick.0 > 0 && *state.get() != AppState::GamePlaying;
+    if tick.0 < tick_limit.0 && !run_ended {
+        return;
+    }
+
+    match player.get_single() {
+        Ok((transform, health)) => info!(
+            "Ran {} ticks; player at {} with {}/{} health",
+            tick.0, transform.translation.truncate(), health.current, health.max,
+        ),
+        Err(_) => info!("Ran {} ticks", tick.0),
+    }
+    exit_events.send(AppExit);
+}
+
+// Checked against the levels built into the game, as the data files haven't been loaded yet.
+fn parse_level(name: &str) -> Result<String, String> {
+    let game_data = GameData::default();
+    match game_data.level(name) {
+        Some(level) => Ok(level.name.clone()),
+        None => Err(format!("unknown level; expected one of {}", game_data.level_names().collect::<Vec<_>>().join(", "))),
+    }
+}
+
+// Like levels, checked against the characters built into the game.
+fn parse_character(id: &str) -> Result<String, String> {
+    let game_data = GameData::default();
+    match game_data.character(id) {
+        Some(character) => Ok(character.id.clone()),
+        None => Err(format!("unknown character; expected one of {}", game_data.character_ids().collect::<Vec<_>>().join(", "))),
+    }
+}
+
+fn parse_resolution(value: &str) -> Result<Vec2, String> {
+    let (width, height) = value.split_once(['x', 'X']).ok_or("expected WIDTHxHEIGHT, e.g. 1280x720")?;
+    let width: u32 = width.trim().parse().map_err(|_| format!("invalid width {:?}", width))?;
+    let height: u32 = height.trim().parse().map_err(|_| format!("invalid height {:?}", height))?;
+
+    if width == 0 || height == 0 {
+        return Err("the window needs a non-zero size".to_string());
+    }
+
+    Ok(Vec2::new(width as f32, height as f32))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<LaunchArgs, clap::Error> {
+        LaunchArgs::try_parse_from(std::iter::once("endless_stream").chain(args.iter().copied()))
+    }
+
+    #[test]
+    fn parses_a_configured_run() {
+        let args = parse(&["--level", "default", "--seed", "42", "--character", "dorian", "--skip-menu", "--windowed", "1280x720"]).unwrap();
+
+        assert_eq!(args, LaunchArgs {
+            level: Some("default".to_string()),
+            seed: Some(42),
+            character: Some("dorian".to_string()),
+            skip_menu: true,
+            windowed: Some(Vec2::new(1280., 720.)),
+            ..default()
+        });
+    }
+
+    #[test]
+    fn rejects_unknown_names_and_bad_resolutions() {
+        assert!(parse(&["--level", "moon"]).is_err());
+        assert!(parse(&["--character", "nobody"]).is_err());
+        assert!(parse(&["--windowed", "1280"]).is_err());
+        assert!(parse(&["--windowed", "0x720"]).is_err());
+    }
+
+    #[test]
+    fn headless_needs_a_tick_count_and_no_window() {
+        assert!(parse(&["--headless"]).is_err());
+        assert!(parse(&["--ticks", "10"]).is_err());
+        assert!(parse(&["--headless", "--ticks", "10", "--windowed", "800x600"]).is_err());
+        assert!(parse(&["--headless", "--ticks", "10"]).is_ok());
+    }
+
+    #[test]
+    fn benchmarks_run_headless_for_a_tick_count() {
+        assert!(parse(&["--benchmark"]).is_err());
+        assert!(parse(&["--enemies", "10", "--headless", "--ticks", "10"]).is_err());
+        assert!(parse(&["--benchmark", "--ticks", "10", "--windowed", "800x600"]).is_err());
+
+        let args = parse(&["--benchmark", "--ticks", "600", "--enemies", "5000", "--props", "200", "--seed", "7"]).unwrap();
+        assert_eq!(args.benchmark_config(), Some(BenchmarkConfig { enemies: 5000, projectiles: 0, props: 200, ticks: 600, seed: 7 }));
+    }
+
+    #[test]
+    fn replays_pick_their_own_setup() {
+        assert!(parse(&["--replay", "replays/last.ron", "--seed", "1"]).is_err());
+        assert!(parse(&["--replay", "replays/last.ron", "--headless", "--ticks", "10"]).is_ok());
+    }
+}
@@ -0,0 +1,115 @@
+use std::collections::BTreeMap;
+
+use bevy::asset::Asset;
+use bevy::reflect::{TypePath, TypeUuid};
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+
+use crate::data::resource::GameData;
+use crate::game::character::Character;
+use crate::game::enemy::archetype::EnemyArchetype;
+use crate::game::level::LevelDefinition;
+use crate::game::loot::DropTable;
+use crate::game::prop::archetype::PropArchetype;
+use crate::game::weapon::component::{WeaponKind, WeaponStats};
+use crate::shop::upgrade::{ShopUpgrade, UpgradeDefinition};
+
+// One of the RON files under `assets/data`, each holding one part of `GameData`.
+pub trait DataFile: Asset + DeserializeOwned + Clone {
+    // Relative to the asset folder. Bevy picks loaders by everything after the first dot of the file name,
+    // so e.g. `game.enemies.ron` is what tells the enemy loader apart from the others.
+    const PATH: &'static str;
+
+    fn apply(self, game_data: &mut GameData);
+}
+
+#[derive(Deserialize, TypeUuid, TypePath, Clone, Debug)]
+#[uuid = "6d0c2a51-8a6e-4c39-9f39-0f2f6c1b7e01"]
+#[serde(transparent)]
+pub struct EnemyData(pub Vec<EnemyArchetype>);
+
+impl DataFile for EnemyData {
+    const PATH: &'static str = "data/game.enemies.ron";
+
+    fn apply(self, game_data: &mut GameData) {
+        game_data.enemies = self.0;
+    }
+}
+
+#[derive(Deserialize, TypeUuid, TypePath, Clone, Debug)]
+#[uuid = "6d0c2a51-8a6e-4c39-9f39-0f2f6c1b7e02"]
+#[serde(transparent)]
+pub struct WeaponData(pub BTreeMap<WeaponKind, WeaponStats>);
+
+impl DataFile for WeaponData {
+    const PATH: &'static str = "data/game.weapons.ron";
+
+    fn apply(self, game_data: &mut GameData) {
+        game_data.weapons = self.0;
+    }
+}
+
+#[derive(Deserialize, TypeUuid, TypePath, Clone, Debug)]
+#[uuid = "6d0c2a51-8a6e-4c39-9f39-0f2f6c1b7e03"]
+#[serde(transparent)]
+pub struct UpgradeData(pub BTreeMap<ShopUpgrade, UpgradeDefinition>);
+
+impl DataFile for UpgradeData {
+    const PATH: &'static str = "data/game.upgrades.ron";
+
+    fn apply(self, game_data: &mut GameData) {
+        game_data.upgrades = self.0;
+    }
+}
+
+#[derive(Deserialize, TypeUuid, TypePath, Clone, Debug)]
+#[uuid = "6d0c2a51-8a6e-4c39-9f39-0f2f6c1b7e04"]
+#[serde(transparent)]
+pub struct LevelData(pub Vec<LevelDefinition>);
+
+impl DataFile for LevelData {
+    const PATH: &'static str = "data/game.levels.ron";
+
+    fn apply(self, game_data: &mut GameData) {
+        game_data.levels = self.0;
+    }
+}
+
+#[derive(Deserialize, TypeUuid, TypePath, Clone, Debug)]
+#[uuid = "6d0c2a51-8a6e-4c39-9f39-0f2f6c1b7e05"]
+#[serde(transparent)]
+pub struct PropData(pub Vec<PropArchetype>);
+
+impl DataFile for PropData {
+    const PATH: &'static str = "data/game.props.ron";
+
+    fn apply(self, game_data: &mut GameData) {
+        game_data.props = self.0;
+    }
+}
+
+#[derive(Deserialize, TypeUuid, TypePath, Clone, Debug)]
+#[uuid = "6d0c2a51-8a6e-4c39-9f39-0f2f6c1b7e06"]
+#[serde(transparent)]
+pub struct LootData(pub BTreeMap<String, DropTable>);
+
+impl DataFile for LootData {
+    const PATH: &'static str = "data/game.loot.ron";
+
+    fn apply(self, game_data: &mut GameData) {
+        game_data.loot = self.0;
+    }
+}
+
+#[derive(Deserialize, TypeUuid, TypePath, Clone, Debug)]
+#[uuid = "6d0c2a51-8a6e-4c39-9f39-0f2f6c1b7e07"]
+#[serde(transparent)]
+pub struct CharacterData(pub Vec<Character>);
+
+impl DataFile for CharacterData {
+    const PATH: &'static str = "data/game.characters.ron";
+
+    fn apply(self, game_data: &mut GameData) {
+        game_data.characters = self.0;
+    }
+}
@@ -0,0 +1,35 @@
+use std::marker::PhantomData;
+
+use bevy::asset::{AssetLoader, Error, LoadContext, LoadedAsset};
+use bevy::utils::BoxedFuture;
+
+use crate::data::asset::DataFile;
+
+// Parses a data file as RON. Syntax errors fail the load, which Bevy logs, and the last good version stays in use.
+pub struct RonLoader<T> {
+    extensions: [&'static str; 1],
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T: DataFile> Default for RonLoader<T> {
+    fn default() -> Self {
+        RonLoader {
+            extensions: [T::PATH.split_once('.').map_or(T::PATH, |(_, extension)| extension)],
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T: DataFile> AssetLoader for RonLoader<T> {
+    fn load<'a>(&'a self, bytes: &'a [u8], load_context: &'a mut LoadContext) -> BoxedFuture<'a, Result<(), Error>> {
+        Box::pin(async move {
+            let data: T = ron::de::from_bytes(bytes)?;
+            load_context.set_default_asset(LoadedAsset::new(data));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &self.extensions
+    }
+}
@@ -0,0 +1,39 @@
+use bevy::prelude::*;
+
+use asset::{CharacterData, DataFile, EnemyData, LevelData, LootData, PropData, UpgradeData, WeaponData};
+use loader::RonLoader;
+use resource::GameData;
+use system::*;
+
+pub mod asset;
+pub mod loader;
+pub mod resource;
+pub mod system;
+
+// Systems that react to changed data run after this, so they see every file that changed in the same frame.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DataReloadSet;
+
+// Levels, characters, enemy and prop archetypes, loot and balance numbers, read from `assets/data` and reloaded whenever the files change.
+pub struct DataPlugin;
+
+impl Plugin for DataPlugin {
+    fn build(&self, app: &mut App) {
+        add_data_file::<EnemyData>(app);
+        add_data_file::<WeaponData>(app);
+        add_data_file::<UpgradeData>(app);
+        add_data_file::<LevelData>(app);
+        add_data_file::<PropData>(app);
+        add_data_file::<LootData>(app);
+        add_data_file::<CharacterData>(app);
+
+        app.init_resource::<GameData>()
+            .add_systems(Startup, load_data_files)
+            .add_systems(Update, reload_data_system.in_set(DataReloadSet));
+    }
+}
+
+fn add_data_file<T: DataFile>(app: &mut App) {
+    app.add_asset::<T>()
+        .init_asset_loader::<RonLoader<T>>();
+}
@@ -0,0 +1,309 @@
+use std::collections::{BTreeMap, HashSet};
+
+use bevy::asset::HandleId;
+use bevy::prelude::*;
+
+use crate::data::asset::{CharacterData, DataFile, EnemyData, LevelData, LootData, PropData, UpgradeData, WeaponData};
+use crate::game::character::{Character, DEFAULT_CHARACTER};
+use crate::game::enemy::archetype::EnemyArchetype;
+use crate::game::level::{LevelDefinition, DEFAULT_LEVEL};
+use crate::game::loot::DropTable;
+use crate::game::prop::archetype::PropArchetype;
+use crate::game::weapon::component::{WeaponKind, WeaponStats};
+use crate::shop::upgrade::{ShopUpgrade, UpgradeDefinition};
+
+// The validated contents of every file under `assets/data`. Starts out as the copies built into the binary,
+// and is replaced whenever a file on disk loads or changes and still passes `validate`.
+#[derive(Resource, Clone, Debug, PartialEq)]
+pub struct GameData {
+    pub enemies: Vec<EnemyArchetype>,
+    pub weapons: BTreeMap<WeaponKind, WeaponStats>,
+    pub upgrades: BTreeMap<ShopUpgrade, UpgradeDefinition>,
+    pub levels: Vec<LevelDefinition>,
+    pub props: Vec<PropArchetype>,
+    pub loot: BTreeMap<String, DropTable>,
+    pub characters: Vec<Character>,
+}
+
+impl Default for GameData {
+    fn default() -> Self {
+        fn embedded<T: DataFile>(source: &str) -> T {
+            ron::from_str(source).unwrap_or_else(|error| panic!("built-in {} is invalid: {}", T::PATH, error))
+        }
+
+        GameData {
+            enemies: embedded::<EnemyData>(include_str!("../../assets/data/game.enemies.ron")).0,
+            weapons: embedded::<WeaponData>(include_str!("../../assets/data/game.weapons.ron")).0,
+            upgrades: embedded::<UpgradeData>(include_str!("../../assets/data/game.upgrades.ron")).0,
+            levels: embedded::<LevelData>(include_str!("../../assets/data/game.levels.ron")).0,
+            props: embedded::<PropData>(include_str!("../../assets/data/game.props.ron")).0,
+            loot: embedded::<LootData>(include_str!("../../assets/data/game.loot.ron")).0,
+            characters: embedded::<CharacterData>(include_str!("../../assets/data/game.characters.ron")).0,
+        }
+    }
+}
+
+impl GameData {
+    pub fn archetype(&self, id: &str) -> Option<&EnemyArchetype> {
+        self.enemies.iter().find(|archetype| archetype.id == id)
+    }
+
+    pub fn prop(&self, id: &str) -> Option<&PropArchetype> {
+        self.props.iter().find(|archetype| archetype.id == id)
+    }
+
+    // Every table an archetype names is guaranteed to be there by `validate`.
+    pub fn drop_table(&self, name: &str) -> &DropTable {
+        &self.loot[name]
+    }
+
+    // Every weapon kind is guaranteed to be there by `validate`.
+    pub fn weapon(&self, kind: WeaponKind) -> &WeaponStats {
+        &self.weapons[&kind]
+    }
+
+    // Every upgrade is guaranteed to be there by `validate`.
+    pub fn upgrade(&self, upgrade: ShopUpgrade) -> &UpgradeDefinition {
+        &self.upgrades[&upgrade]
+    }
+
+    pub fn level(&self, name: &str) -> Option<&LevelDefinition> {
+        self.levels.iter().find(|level| level.name == name)
+    }
+
+    pub fn character(&self, id: &str) -> Option<&Character> {
+        self.characters.iter().find(|character| character.id == id)
+    }
+
+    pub fn character_ids(&self) -> impl Iterator<Item = &str> {
+        self.characters.iter().map(|character| character.id.as_str())
+    }
+
+    pub fn level_names(&self) -> impl Iterator<Item = &str> {
+        self.levels.iter().map(|level| level.name.as_str())
+    }
+
+    // Checks the data as a whole, since e.g. levels refer to enemy archetypes by id.
+    pub fn validate(&self) -> Result<(), String> {
+        for (name, table) in &self.loot {
+            table.validate().map_err(|error| format!("drop table {}: {}", name, error))?;
+        }
+
+        let mut enemy_ids = HashSet::new();
+        for archetype in &self.enemies {
+            archetype.validate()?;
+            if !enemy_ids.insert(archetype.id.as_str()) {
+                return Err(format!("enemy {} is defined twice", archetype.id));
+            }
+            if !self.loot.contains_key(&archetype.drops) {
+                return Err(format!("enemy {} drops from unknown table {}", archetype.id, archetype.drops));
+            }
+        }
+
+        for kind in WeaponKind::ALL {
+            let stats = self.weapons.get(&kind).ok_or_else(|| format!("weapon {} is missing", kind.name()))?;
+            stats.validate().map_err(|error| format!("weapon {}: {}", kind.name(), error))?;
+            if let Some(evolution) = stats.evolution.filter(|evolution| !evolution.into.is_evolved()) {
+                return Err(format!("weapon {} evolves into {}, which isn't an evolved weapon", kind.name(), evolution.into.name()));
+            }
+        }
+
+        // Following the recipes from any weapon has to end before it's seen every kind, or they go round in a loop.
+        for kind in WeaponKind::ALL {
+            let mut current = kind;
+            for _ in 0..WeaponKind::ALL.len() {
+                let Some(evolution) = self.weapon(current).evolution else { break };
+                if evolution.into == kind {
+                    return Err(format!("weapon {} evolves back into itself", kind.name()));
+                }
+                current = evolution.into;
+            }
+        }
+
+        for upgrade in ShopUpgrade::ALL {
+            let definition = self.upgrades.get(&upgrade).ok_or_else(|| format!("upgrade {} is missing", upgrade.name()))?;
+            definition.validate().map_err(|error| format!("upgrade {}: {}", upgrade.name(), error))?;
+        }
+
+        let mut prop_ids = HashSet::new();
+        for archetype in &self.props {
+            archetype.validate()?;
+            if !prop_ids.insert(archetype.id.as_str()) {
+                return Err(format!("prop {} is defined twice", archetype.id));
+            }
+            if !self.loot.contains_key(&archetype.drops) {
+                return Err(format!("prop {} drops from unknown table {}", archetype.id, archetype.drops));
+            }
+        }
+
+        let mut level_names = HashSet::new();
+        for level in &self.levels {
+            level.validate()?;
+            if !level_names.insert(level.name.as_str()) {
+                return Err(format!("level {} is defined twice", level.name));
+            }
+
+            for archetype in [&level.enemy_ring.archetype, &level.enemy_ring.elite_archetype] {
+                match self.archetype(archetype) {
+                    None => return Err(format!("level {} spawns unknown enemy {}", level.name, archetype)),
+                    Some(archetype) if archetype.boss => {
+                        return Err(format!("level {} spawns boss {} in its enemy ring", level.name, archetype.id));
+                    }
+                    Some(_) => {}
+                }
+            }
+
+            if let Some(boss) = &level.boss {
+                match self.archetype(&boss.archetype) {
+                    None => return Err(format!("level {} spawns unknown enemy {}", level.name, boss.archetype)),
+                    Some(archetype) if !archetype.boss => {
+                        return Err(format!("level {} spawns {} as its boss, which isn't one", level.name, archetype.id));
+                    }
+                    Some(_) => {}
+                }
+            }
+
+            for prop in &level.props {
+                if !prop_ids.contains(prop.archetype.as_str()) {
+                    return Err(format!("level {} places unknown prop {}", level.name, prop.archetype));
+                }
+            }
+        }
+
+        if !level_names.contains(DEFAULT_LEVEL) {
+            return Err(format!("the {} level is missing", DEFAULT_LEVEL));
+        }
+
+        let mut character_ids = HashSet::new();
+        for character in &self.characters {
+            character.validate()?;
+            if !character_ids.insert(character.id.as_str()) {
+                return Err(format!("character {} is defined twice", character.id));
+            }
+            if character.starting_weapon.is_evolved() {
+                return Err(format!("character {} starts with evolved weapon {}", character.id, character.starting_weapon.name()));
+            }
+        }
+
+        if !character_ids.contains(DEFAULT_CHARACTER) {
+            return Err(format!("the {} character is missing", DEFAULT_CHARACTER));
+        }
+
+        Ok(())
+    }
+}
+
+// Handles to the data files, kept around so the asset watcher reports when they change.
+#[derive(Resource)]
+pub struct DataFiles {
+    pub enemies: Handle<EnemyData>,
+    pub weapons: Handle<WeaponData>,
+    pub upgrades: Handle<UpgradeData>,
+    pub levels: Handle<LevelData>,
+    pub props: Handle<PropData>,
+    pub loot: Handle<LootData>,
+    pub characters: Handle<CharacterData>,
+}
+
+impl DataFiles {
+    pub fn ids(&self) -> [HandleId; 7] {
+        [
+            self.enemies.id(),
+            self.weapons.id(),
+            self.upgrades.id(),
+            self.levels.id(),
+            self.props.id(),
+            self.loot.id(),
+            self.characters.id(),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::passive::component::PassiveKind;
+    use crate::game::weapon::evolution::Evolution;
+
+    #[test]
+    fn built_in_data_is_valid() {
+        assert_eq!(GameData::default().validate(), Ok(()));
+    }
+
+    #[test]
+    fn rejects_levels_spawning_unknown_enemies() {
+        let mut game_data = GameData::default();
+        game_data.enemies.retain(|archetype| archetype.id != "brute");
+
+        assert!(game_data.validate().unwrap_err().contains("unknown enemy brute"));
+    }
+
+    #[test]
+    fn rejects_bosses_that_arent_bosses() {
+        let mut game_data = GameData::default();
+        game_data.levels[0].boss.as_mut().unwrap().archetype = "brute".to_string();
+
+        assert!(game_data.validate().unwrap_err().contains("spawns brute as its boss"));
+    }
+
+    #[test]
+    fn rejects_levels_placing_unknown_props() {
+        let mut game_data = GameData::default();
+        game_data.props.retain(|archetype| archetype.id != "crate");
+
+        assert!(game_data.validate().unwrap_err().contains("unknown prop crate"));
+    }
+
+    #[test]
+    fn rejects_archetypes_dropping_from_unknown_tables() {
+        let mut game_data = GameData::default();
+        game_data.loot.remove("elite");
+
+        assert!(game_data.validate().unwrap_err().contains("drops from unknown table elite"));
+    }
+
+    #[test]
+    fn rejects_a_missing_default_character() {
+        let mut game_data = GameData::default();
+        game_data.characters.retain(|character| character.id != DEFAULT_CHARACTER);
+
+        assert_eq!(game_data.validate(), Err(format!("the {} character is missing", DEFAULT_CHARACTER)));
+    }
+
+    #[test]
+    fn rejects_missing_weapons() {
+        let mut game_data = GameData::default();
+        game_data.weapons.remove(&WeaponKind::Axe);
+
+        assert_eq!(game_data.validate(), Err("weapon Axe is missing".to_string()));
+    }
+
+    fn set_evolution(game_data: &mut GameData, kind: WeaponKind, into: WeaponKind) {
+        game_data.weapons.get_mut(&kind).unwrap().evolution = Some(Evolution { passive: PassiveKind::Might, into });
+    }
+
+    #[test]
+    fn rejects_evolutions_into_regular_weapons() {
+        let mut game_data = GameData::default();
+        set_evolution(&mut game_data, WeaponKind::Flurry, WeaponKind::Knife);
+
+        assert_eq!(game_data.validate(), Err("weapon Flurry evolves into Knife, which isn't an evolved weapon".to_string()));
+    }
+
+    #[test]
+    fn rejects_evolutions_that_loop() {
+        let mut game_data = GameData::default();
+        set_evolution(&mut game_data, WeaponKind::Flurry, WeaponKind::Cleaver);
+        set_evolution(&mut game_data, WeaponKind::Cleaver, WeaponKind::Flurry);
+
+        assert!(game_data.validate().unwrap_err().contains("evolves back into itself"));
+    }
+
+    #[test]
+    fn rejects_characters_starting_with_evolved_weapons() {
+        let mut game_data = GameData::default();
+        game_data.characters[0].starting_weapon = WeaponKind::Cleaver;
+
+        assert!(game_data.validate().unwrap_err().contains("starts with evolved weapon Cleaver"));
+    }
+}
@@ -0,0 +1,103 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+
+use crate::data::asset::{CharacterData, DataFile, EnemyData, LevelData, LootData, PropData, UpgradeData, WeaponData};
+use crate::data::resource::{DataFiles, GameData};
+
+pub fn load_data_files(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(DataFiles {
+        enemies: asset_server.load(EnemyData::PATH),
+        weapons: asset_server.load(WeaponData::PATH),
+        upgrades: asset_server.load(UpgradeData::PATH),
+        levels: asset_server.load(LevelData::PATH),
+        props: asset_server.load(PropData::PATH),
+        loot: asset_server.load(LootData::PATH),
+        characters: asset_server.load(CharacterData::PATH),
+    });
+}
+
+// Every data file as it was last loaded, and whether any of them (re)loaded since the last look.
+#[derive(SystemParam)]
+pub struct LoadedDataFiles<'w, 's> {
+    files: Option<Res<'w, DataFiles>>,
+    enemies: LoadedDataFile<'w, 's, EnemyData>,
+    weapons: LoadedDataFile<'w, 's, WeaponData>,
+    upgrades: LoadedDataFile<'w, 's, UpgradeData>,
+    levels: LoadedDataFile<'w, 's, LevelData>,
+    props: LoadedDataFile<'w, 's, PropData>,
+    loot: LoadedDataFile<'w, 's, LootData>,
+    characters: LoadedDataFile<'w, 's, CharacterData>,
+}
+
+impl LoadedDataFiles<'_, '_> {
+    pub fn changed(&mut self) -> bool {
+        // Not short-circuiting, so every file's events are read.
+        self.enemies.changed()
+            | self.weapons.changed()
+            | self.upgrades.changed()
+            | self.levels.changed()
+            | self.props.changed()
+            | self.loot.changed()
+            | self.characters.changed()
+    }
+
+    // The built-in data with every file that has loaded laid over it, so the newest version of each file
+    // ends up in the result together.
+    pub fn game_data(&self) -> GameData {
+        let mut game_data = GameData::default();
+        if let Some(files) = &self.files {
+            self.enemies.apply(&files.enemies, &mut game_data);
+            self.weapons.apply(&files.weapons, &mut game_data);
+            self.upgrades.apply(&files.upgrades, &mut game_data);
+            self.levels.apply(&files.levels, &mut game_data);
+            self.props.apply(&files.props, &mut game_data);
+            self.loot.apply(&files.loot, &mut game_data);
+            self.characters.apply(&files.characters, &mut game_data);
+        }
+
+        game_data
+    }
+}
+
+#[derive(SystemParam)]
+pub struct LoadedDataFile<'w, 's, T: DataFile> {
+    events: EventReader<'w, 's, AssetEvent<T>>,
+    assets: Res<'w, Assets<T>>,
+}
+
+impl<T: DataFile> LoadedDataFile<'_, '_, T> {
+    fn changed(&mut self) -> bool {
+        // Reads every event, so none are left over for the next frame.
+        let mut changed = false;
+        for event in self.events.iter() {
+            changed |= matches!(event, AssetEvent::Created { .. } | AssetEvent::Modified { .. });
+        }
+
+        changed
+    }
+
+    fn apply(&self, handle: &Handle<T>, game_data: &mut GameData) {
+        if let Some(data_file) = self.assets.get(handle) {
+            data_file.clone().apply(game_data);
+        }
+    }
+}
+
+// Rebuilds `GameData` from every data file whenever any of them (re)loads, so edits that only make sense
+// together are checked together. If the result is invalid the error is logged and the previous data stays
+// in use until the files are fixed.
+pub fn reload_data_system(mut loaded: LoadedDataFiles, mut game_data: ResMut<GameData>) {
+    if !loaded.changed() {
+        return;
+    }
+
+    let reloaded = loaded.game_data();
+    match reloaded.validate() {
+        Ok(()) if reloaded != *game_data => {
+            info!("Applied changes to the data files");
+            *game_data = reloaded;
+        }
+        Ok(()) => {}
+        Err(error) => error!("Ignoring the data files until they're fixed: {}", error),
+    }
+}
@@ -0,0 +1,111 @@
+use bevy::prelude::*;
+
+use crate::AppState;
+use crate::game::game_state::GameState;
+use crate::game::weapon::component::WeaponKind;
+
+// Most enemies one `spawn` command will add, so a typo doesn't grind the game to a halt.
+const MAX_SPAWN_COUNT: u32 = 500;
+
+pub const HELP: &str =
+    "Commands: spawn <archetype> <n>, god, give gold <n>, give xp <n>, give weapon <kind>, setstate <state>, teleport <x> <y>, help";
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ConsoleCommand {
+    Spawn { archetype: String, count: u32 },
+    // Toggles whether the player can take damage.
+    God,
+    GiveGold(u32),
+    GiveExperience(u32),
+    GiveWeapon(WeaponKind),
+    SetState(StateChange),
+    Teleport(Vec2),
+    Help,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum StateChange {
+    App(AppState),
+    Game(GameState),
+}
+
+pub fn parse_command(line: &str) -> Result<ConsoleCommand, String> {
+    let words: Vec<&str> = line.split_whitespace().collect();
+
+    match words.as_slice() {
+        ["spawn", archetype] => Ok(ConsoleCommand::Spawn { archetype: archetype.to_string(), count: 1 }),
+        ["spawn", archetype, count] => {
+            let count: u32 = parse_number(count)?;
+            if count == 0 || count > MAX_SPAWN_COUNT {
+                return Err(format!("can spawn between 1 and {} enemies at a time", MAX_SPAWN_COUNT));
+            }
+
+            Ok(ConsoleCommand::Spawn { archetype: archetype.to_string(), count })
+        }
+        ["god"] => Ok(ConsoleCommand::God),
+        ["give", "gold", amount] => Ok(ConsoleCommand::GiveGold(parse_number(amount)?)),
+        ["give", "weapon", kind] => parse_weapon(kind).map(ConsoleCommand::GiveWeapon),
+        ["give", "xp", amount] => Ok(ConsoleCommand::GiveExperience(parse_number(amount)?)),
+        ["setstate", state] => parse_state(state).map(ConsoleCommand::SetState),
+        ["teleport", x, y] => Ok(ConsoleCommand::Teleport(Vec2::new(parse_number(x)?, parse_number(y)?))),
+        ["help"] => Ok(ConsoleCommand::Help),
+        [] => Err("nothing to do".to_string()),
+        [command, ..] => Err(format!("can't make sense of that {}; try help", command)),
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(word: &str) -> Result<T, String> {
+    word.parse().map_err(|_| format!("{} isn't a valid number", word))
+}
+
+fn parse_weapon(name: &str) -> Result<WeaponKind, String> {
+    WeaponKind::ALL.into_iter()
+        .find(|kind| kind.name().eq_ignore_ascii_case(name))
+        .ok_or_else(|| format!("unknown weapon {}", name))
+}
+
+fn parse_state(name: &str) -> Result<StateChange, String> {
+    let state = match name.to_lowercase().as_str() {
+        "mainmenu" => StateChange::App(AppState::MainMenu),
+        "gameplaying" => StateChange::App(AppState::GamePlaying),
+        "running" => StateChange::Game(GameState::Running),
+        "paused" => StateChange::Game(GameState::Paused),
+        "cutscene" => StateChange::Game(GameState::Cutscene),
+        "levelcomplete" => StateChange::Game(GameState::LevelComplete),
+        _ => return Err(format!(
+            "unknown state {}; expected MainMenu, GamePlaying, Running, Paused, Cutscene or LevelComplete", name,
+        )),
+    };
+
+    Ok(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_every_command() {
+        assert_eq!(parse_command("spawn brute 20"), Ok(ConsoleCommand::Spawn { archetype: "brute".to_string(), count: 20 }));
+        assert_eq!(parse_command("spawn grunt"), Ok(ConsoleCommand::Spawn { archetype: "grunt".to_string(), count: 1 }));
+        assert_eq!(parse_command("god"), Ok(ConsoleCommand::God));
+        assert_eq!(parse_command("give gold 500"), Ok(ConsoleCommand::GiveGold(500)));
+        assert_eq!(parse_command("give xp 500"), Ok(ConsoleCommand::GiveExperience(500)));
+        assert_eq!(parse_command("give weapon halo"), Ok(ConsoleCommand::GiveWeapon(WeaponKind::Halo)));
+        assert_eq!(parse_command("setstate Paused"), Ok(ConsoleCommand::SetState(StateChange::Game(GameState::Paused))));
+        assert_eq!(parse_command("setstate mainmenu"), Ok(ConsoleCommand::SetState(StateChange::App(AppState::MainMenu))));
+        assert_eq!(parse_command("  teleport -100  42.5 "), Ok(ConsoleCommand::Teleport(Vec2::new(-100., 42.5))));
+    }
+
+    #[test]
+    fn rejects_malformed_commands() {
+        assert!(parse_command("").is_err());
+        assert!(parse_command("spawn brute lots").is_err());
+        assert!(parse_command("spawn brute 0").is_err());
+        assert!(parse_command("give xp lots").is_err());
+        assert!(parse_command("give weapon spoon").is_err());
+        assert!(parse_command("setstate Sleeping").is_err());
+        assert!(parse_command("teleport 1").is_err());
+        assert!(parse_command("fly").is_err());
+    }
+}
@@ -0,0 +1,10 @@
+use bevy::prelude::*;
+
+#[derive(Component)]
+pub struct DebugOverlayText;
+
+#[derive(Component)]
+pub struct DebugConsoleRoot;
+
+#[derive(Component)]
+pub struct DebugConsoleText;
@@ -0,0 +1,6 @@
+use bevy::prelude::*;
+
+use crate::debug::command::ConsoleCommand;
+
+#[derive(Event)]
+pub struct ConsoleCommandEvent(pub ConsoleCommand);
@@ -0,0 +1,38 @@
+use bevy::diagnostic::FrameTimeDiagnosticsPlugin;
+use bevy::input::InputSystem;
+use bevy::prelude::*;
+
+use event::ConsoleCommandEvent;
+use resource::{DebugConsole, DebugOverlay};
+use system::*;
+
+pub mod command;
+pub mod component;
+pub mod event;
+pub mod resource;
+pub mod system;
+
+// Developer tools: F3 shows an overlay with colliders, FPS and entity counts, and ` opens a console for cheats.
+// The overlay draws with gizmos, so like audio this is only added to the windowed game.
+pub struct DebugPlugin;
+
+impl Plugin for DebugPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<FrameTimeDiagnosticsPlugin>() {
+            app.add_plugins(FrameTimeDiagnosticsPlugin);
+        }
+
+        app.add_event::<ConsoleCommandEvent>()
+            .init_resource::<DebugOverlay>()
+            .init_resource::<DebugConsole>()
+            .add_systems(Startup, setup_debug_ui)
+            .add_systems(PreUpdate, console_input_system.after(InputSystem))
+            .add_systems(Update, (
+                toggle_overlay_system,
+                console_command_system,
+                console_ui_system,
+                overlay_text_system,
+                collider_gizmo_system.run_if(|overlay: Res<DebugOverlay>| overlay.visible),
+            ));
+    }
+}
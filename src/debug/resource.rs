@@ -0,0 +1,25 @@
+use bevy::prelude::*;
+
+// How many lines of output the console keeps around.
+const CONSOLE_LOG_LINES: usize = 10;
+
+#[derive(Resource, Default)]
+pub struct DebugOverlay {
+    pub visible: bool,
+}
+
+#[derive(Resource, Default)]
+pub struct DebugConsole {
+    pub open: bool,
+    pub input: String,
+    pub log: Vec<String>,
+}
+
+impl DebugConsole {
+    pub fn print(&mut self, line: impl Into<String>) {
+        self.log.push(line.into());
+        if self.log.len() > CONSOLE_LOG_LINES {
+            self.log.remove(0);
+        }
+    }
+}
@@ -0,0 +1,337 @@
+use std::f32::consts::TAU;
+
+use bevy::diagnostic::{DiagnosticsStore, FrameTimeDiagnosticsPlugin};
+use bevy::ecs::query::Has;
+use bevy::prelude::*;
+
+use crate::AppState;
+use crate::data::resource::GameData;
+use crate::debug::command::{parse_command, ConsoleCommand, StateChange, HELP};
+use crate::debug::component::{DebugConsoleRoot, DebugConsoleText, DebugOverlayText};
+use crate::debug::event::ConsoleCommandEvent;
+use crate::debug::resource::{DebugConsole, DebugOverlay};
+use crate::game::GameEntity;
+use crate::game::enemy::component::Enemy;
+use crate::game::enemy::system::spawn_enemy_at;
+use crate::game::feedback::component::DamageNumber;
+use crate::game::game_state::GameState;
+use crate::game::health::component::{Health, Invulnerable};
+use crate::game::level::component::LevelFloor;
+use crate::game::level::{LevelDefinition, SelectedLevel};
+use crate::game::movement::component::{Collidable, Movable};
+use crate::game::pickup::component::Pickup;
+use crate::game::player::component::{Experience, PlayerControlled};
+use crate::game::pool::resource::{EntityPool, PoolKind};
+use crate::game::prop::component::Prop;
+use crate::game::simulation::SIMULATION_TICK_SECS;
+use crate::game::simulation::resource::SimulationTick;
+use crate::game::weapon::component::{Projectile, Weapon};
+use crate::loading::resource::GameAssets;
+use crate::menu::MenuState;
+use crate::save::resource::CurrentRun;
+
+const OVERLAY_TOGGLE_KEY: KeyCode = KeyCode::F3;
+const CONSOLE_TOGGLE_KEY: KeyCode = KeyCode::Grave;
+// Enemies spawned from the console appear in a ring this far from the player.
+const CONSOLE_SPAWN_RADIUS: f32 = 300.;
+
+pub fn setup_debug_ui(mut commands: Commands) {
+    let text_style = TextStyle {
+        font_size: 18.,
+        color: Color::WHITE,
+        ..default()
+    };
+
+    commands.spawn((
+        TextBundle {
+            visibility: Visibility::Hidden,
+            z_index: ZIndex::Global(100),
+            ..TextBundle::from_section("", text_style.clone())
+                .with_style(Style {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(10.),
+                    left: Val::Px(10.),
+                    ..default()
+                })
+                .with_background_color(Color::rgba(0., 0., 0., 0.6))
+        },
+        DebugOverlayText,
+    ));
+
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(0.),
+                width: Val::Percent(100.),
+                padding: UiRect::all(Val::Px(8.)),
+                ..default()
+            },
+            background_color: Color::rgba(0., 0., 0., 0.8).into(),
+            visibility: Visibility::Hidden,
+            z_index: ZIndex::Global(101),
+            ..default()
+        },
+        DebugConsoleRoot,
+    )).with_children(|parent| {
+        parent.spawn((TextBundle::from_section("", text_style), DebugConsoleText));
+    });
+}
+
+pub fn toggle_overlay_system(keyboard_input: Res<Input<KeyCode>>, mut overlay: ResMut<DebugOverlay>) {
+    if keyboard_input.just_pressed(OVERLAY_TOGGLE_KEY) {
+        overlay.visible = !overlay.visible;
+    }
+}
+
+// Runs right after input is collected. While the console is open it takes all keyboard input for itself,
+// so typing doesn't also move the player or press buttons in the menus.
+pub fn console_input_system(
+    mut keyboard_input: ResMut<Input<KeyCode>>,
+    mut characters: EventReader<ReceivedCharacter>,
+    mut console: ResMut<DebugConsole>,
+    mut command_events: EventWriter<ConsoleCommandEvent>,
+) {
+    if keyboard_input.just_pressed(CONSOLE_TOGGLE_KEY) {
+        console.open = !console.open;
+        characters.clear();
+        keyboard_input.reset_all();
+        return;
+    }
+
+    if !console.open {
+        characters.clear();
+        return;
+    }
+
+    for character in characters.iter() {
+        if !character.char.is_control() {
+            console.input.push(character.char);
+        }
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Back) {
+        console.input.pop();
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Return) {
+        let line = std::mem::take(&mut console.input);
+        console.print(format!("> {}", line));
+
+        match parse_command(&line) {
+            Ok(command) => command_events.send(ConsoleCommandEvent(command)),
+            Err(error) => console.print(error),
+        }
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Escape) {
+        console.open = false;
+    }
+
+    keyboard_input.reset_all();
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn console_command_system(
+    mut commands: Commands,
+    mut command_events: EventReader<ConsoleCommandEvent>,
+    mut console: ResMut<DebugConsole>,
+    game_data: Res<GameData>,
+    game_assets: Option<Res<GameAssets>>,
+    mut enemy_pool: ResMut<EntityPool<Enemy>>,
+    mut current_run: ResMut<CurrentRun>,
+    mut players: Query<(Entity, &mut Transform, Has<Invulnerable>), With<PlayerControlled>>,
+    mut experience: Query<&mut Experience>,
+    mut app_state: ResMut<NextState<AppState>>,
+    mut menu_state: ResMut<NextState<MenuState>>,
+    mut game_state: ResMut<NextState<GameState>>,
+) {
+    for ConsoleCommandEvent(command) in command_events.iter() {
+        let player = players.get_single_mut().ok();
+
+        let reply = match (command.clone(), player, &game_assets) {
+            (ConsoleCommand::Help, _, _) => HELP.to_string(),
+            (ConsoleCommand::SetState(StateChange::App(state)), _, _) => {
+                app_state.set(state);
+                menu_state.set(if state == AppState::MainMenu { MenuState::MainMenu } else { MenuState::Disabled });
+                format!("Switching to {:?}", state)
+            }
+            (ConsoleCommand::SetState(StateChange::Game(state)), _, _) => {
+                game_state.set(state);
+                format!("Switching to {:?}", state)
+            }
+            (_, None, _) | (_, _, None) => "Only works during a run".to_string(),
+            (ConsoleCommand::Spawn { archetype, count }, Some((_, transform, _)), Some(game_assets)) => {
+                match game_data.archetype(&archetype) {
+                    Some(archetype) => {
+                        let center = transform.translation.truncate();
+                        for i in 0..count {
+                            let angle = TAU * i as f32 / count as f32;
+                            let position = center + Vec2::from_angle(angle) * CONSOLE_SPAWN_RADIUS;
+                            spawn_enemy_at(&mut commands, game_assets, &mut enemy_pool, archetype, position.extend(0.));
+                        }
+
+                        format!("Spawned {} {}", count, archetype.id)
+                    }
+                    None => format!("Unknown archetype {}", archetype),
+                }
+            }
+            (ConsoleCommand::God, Some((player, _, invulnerable)), _) => {
+                if invulnerable {
+                    commands.entity(player).remove::<Invulnerable>();
+                    "God mode off".to_string()
+                } else {
+                    commands.entity(player).insert(Invulnerable);
+                    "God mode on".to_string()
+                }
+            }
+            (ConsoleCommand::GiveGold(amount), _, _) => {
+                current_run.gold_earned += amount;
+                format!("Gave {} gold", amount)
+            }
+            (ConsoleCommand::GiveExperience(amount), Some((player, _, _)), _) => {
+                // Any levels this is worth are gained on the next tick, same as from kills.
+                match experience.get_mut(player) {
+                    Ok(mut experience) => {
+                        experience.points += amount;
+                        format!("Gave {} experience", amount)
+                    }
+                    Err(_) => "The player can't gain experience".to_string(),
+                }
+            }
+            (ConsoleCommand::GiveWeapon(kind), Some((player, _, _)), _) => {
+                commands.entity(player).with_children(|parent| {
+                    parent.spawn((Weapon::new(kind, game_data.weapon(kind)), GameEntity));
+                });
+                format!("Gave {}", kind.name())
+            }
+            (ConsoleCommand::Teleport(position), Some((_, mut transform, _)), _) => {
+                transform.translation = position.extend(transform.translation.z);
+                format!("Teleported to {}", position)
+            }
+        };
+
+        console.print(reply);
+    }
+}
+
+pub fn console_ui_system(
+    console: Res<DebugConsole>,
+    mut roots: Query<&mut Visibility, With<DebugConsoleRoot>>,
+    mut texts: Query<&mut Text, With<DebugConsoleText>>,
+) {
+    if !console.is_changed() {
+        return;
+    }
+
+    for mut visibility in &mut roots {
+        *visibility = if console.open { Visibility::Inherited } else { Visibility::Hidden };
+    }
+
+    for mut text in &mut texts {
+        let mut lines = console.log.clone();
+        lines.push(format!("> {}_", console.input));
+        text.sections[0].value = lines.join("\n");
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn overlay_text_system(
+    overlay: Res<DebugOverlay>,
+    diagnostics: Res<DiagnosticsStore>,
+    entities: Query<()>,
+    enemy_pool: Res<EntityPool<Enemy>>,
+    projectile_pool: Res<EntityPool<Projectile>>,
+    pickup_pool: Res<EntityPool<Pickup>>,
+    damage_number_pool: Res<EntityPool<DamageNumber>>,
+    props: Query<(), With<Prop>>,
+    game_data: Res<GameData>,
+    selected_level: Res<SelectedLevel>,
+    players: Query<(&Transform, &Health, &Experience, Has<Invulnerable>), With<PlayerControlled>>,
+    tick: Res<SimulationTick>,
+    mut texts: Query<(&mut Text, &mut Visibility), With<DebugOverlayText>>,
+) {
+    for (mut text, mut visibility) in &mut texts {
+        if !overlay.visible {
+            *visibility = Visibility::Hidden;
+            continue;
+        }
+        *visibility = Visibility::Inherited;
+
+        let fps = diagnostics.get(FrameTimeDiagnosticsPlugin::FPS).and_then(|fps| fps.smoothed()).unwrap_or(0.);
+        let mut lines = vec![
+            format!("FPS: {:.0}", fps),
+            format!("Tick: {}", tick.0),
+            format!("Entities: {}", entities.iter().count()),
+            pool_line(&enemy_pool),
+            pool_line(&projectile_pool),
+            pool_line(&pickup_pool),
+            pool_line(&damage_number_pool),
+            format!("Props: {}", props.iter().count()),
+        ];
+        if let Some(level) = game_data.level(&selected_level.0) {
+            lines.extend(spawner_lines(level, tick.0));
+        }
+
+        if let Ok((transform, health, experience, invulnerable)) = players.get_single() {
+            lines.push(format!(
+                "Player: {:.0} with {:.0}/{:.0} health{}",
+                transform.translation.truncate(), health.current, health.max, if invulnerable { " (god)" } else { "" },
+            ));
+            lines.push(format!(
+                "Level {}: {}/{} experience",
+                experience.level, experience.points, Experience::to_next_level(experience.level),
+            ));
+        }
+
+        text.sections[0].value = lines.join("\n");
+    }
+}
+
+// Pools are how everything that comes and goes mid-run is spawned, so their numbers double as the spawner's state.
+fn pool_line<K: PoolKind>(pool: &EntityPool<K>) -> String {
+    let metrics = pool.metrics;
+    format!(
+        "{}: {} in use, {} pooled ({} reused, {} spawned)",
+        K::NAME, pool.in_use(), pool.free_count(), metrics.hits, metrics.misses,
+    )
+}
+
+// The enemy ring is spawned once when the run starts; after that only the boss is still to come.
+fn spawner_lines(level: &LevelDefinition, tick: u64) -> [String; 2] {
+    let ring = &level.enemy_ring;
+    let boss = match &level.boss {
+        None => "Boss: none".to_string(),
+        Some(boss) if tick < boss.tick() => format!(
+            "Boss: {} in {:.0}s (tick {})",
+            boss.archetype, (boss.tick() - tick) as f32 * SIMULATION_TICK_SECS, boss.tick(),
+        ),
+        Some(boss) => format!("Boss: {} spawned at tick {}", boss.archetype, boss.tick()),
+    };
+
+    [
+        format!(
+            "Level {}: ring of {} at {:.0}, {} with {} every {} degrees",
+            level.name, ring.spawns().count(), ring.radius, ring.archetype, ring.elite_archetype, ring.elite_every_degrees,
+        ),
+        boss,
+    ]
+}
+
+// Outlines what the movement system collides with: moving colliders in yellow, static ones in red, and the level bounds.
+pub fn collider_gizmo_system(
+    mut gizmos: Gizmos,
+    colliders: Query<(&Transform, &Collidable, Has<Movable>)>,
+    levels: Query<(&Transform, &Sprite), With<LevelFloor>>,
+) {
+    for (transform, collidable, movable) in &colliders {
+        let color = if movable { Color::YELLOW } else { Color::RED };
+        gizmos.rect_2d(transform.translation.truncate(), 0., collidable.size(), color);
+    }
+
+    for (transform, sprite) in &levels {
+        if let Some(size) = sprite.custom_size {
+            gizmos.rect_2d(transform.translation.truncate(), 0., size, Color::CYAN);
+        }
+    }
+}
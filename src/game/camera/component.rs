@@ -0,0 +1,32 @@
+use bevy::prelude::*;
+
+// Marks the entity the game camera follows; there should only ever be one.
+#[derive(Component)]
+pub struct CameraTarget;
+
+#[derive(Component)]
+pub struct GameCamera {
+    // How quickly the camera catches up with its target, per second; 0 snaps straight to it.
+    pub smoothing: f32,
+    // Half-size of the box around the camera centre the target can move within without the camera following.
+    pub dead_zone: Option<Vec2>,
+    // Where the camera is looking before screen shake is applied.
+    pub focus: Vec2,
+}
+
+// Trauma based screen shake; trauma is added on hits and decays over time,
+// with the actual offset scaling by trauma squared so small hits stay subtle.
+#[derive(Component)]
+pub struct CameraShake {
+    pub trauma: f32,
+    // Trauma lost per second.
+    pub decay: f32,
+    // Offset in world units at full trauma.
+    pub max_offset: f32,
+}
+
+impl CameraShake {
+    pub fn add_trauma(&mut self, amount: f32) {
+        self.trauma = (self.trauma + amount).min(1.);
+    }
+}
@@ -0,0 +1,55 @@
+use bevy::prelude::*;
+
+use crate::AppState;
+use crate::game::GameEntity;
+use crate::replay::resource::ReplayPlayback;
+use crate::settings::resource::Settings;
+use component::{CameraShake, GameCamera};
+use resource::ViewExtents;
+use system::*;
+
+pub mod component;
+pub mod resource;
+pub mod system;
+
+pub struct CameraPlugin;
+
+impl Plugin for CameraPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ViewExtents>()
+            .add_systems(OnEnter(AppState::GamePlaying), spawn_game_camera)
+            .add_systems(Update, (
+                camera_zoom_system,
+                view_extents_system.run_if(not(resource_exists::<ReplayPlayback>())),
+                camera_shake_on_hit_system,
+                camera_follow_system,
+                camera_shake_system,
+            ).chain().run_if(in_state(AppState::GamePlaying)));
+    }
+}
+
+fn spawn_game_camera(mut commands: Commands, settings: Res<Settings>) {
+    commands.spawn(
+        (
+            Camera2dBundle {
+                transform: Transform::from_translation(Vec3::new(0., 0., 999.)),
+                projection: OrthographicProjection {
+                    scale: settings.camera_zoom,
+                    ..default()
+                },
+                ..default()
+            },
+            GameCamera {
+                smoothing: 8.,
+                dead_zone: Some(Vec2::new(20., 15.)),
+                focus: Vec2::ZERO,
+            },
+            CameraShake {
+                trauma: 0.,
+                decay: 1.5,
+                max_offset: 12.,
+            },
+            GameEntity,
+        )
+    );
+}
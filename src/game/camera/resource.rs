@@ -0,0 +1,14 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// Half the size of the area the game camera shows, in world units, for gameplay that reaches "everything on screen".
+// Kept up to date from the camera outside of replays, and recorded into them so a replay plays out the same in a
+// window of any size. Starts out as a 1280x720 window at the default zoom, which is also what headless runs use.
+#[derive(Resource, Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct ViewExtents(pub Vec2);
+
+impl Default for ViewExtents {
+    fn default() -> Self {
+        ViewExtents(Vec2::new(320., 180.))
+    }
+}
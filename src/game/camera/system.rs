@@ -0,0 +1,145 @@
+use bevy::prelude::*;
+
+use crate::game::camera::component::{CameraShake, CameraTarget, GameCamera};
+use crate::game::camera::resource::ViewExtents;
+use crate::game::health::event::DamageEvent;
+use crate::game::level::component::LevelFloor;
+use crate::settings::resource::Settings;
+
+// Trauma added to the camera shake for each hit the camera target takes.
+const TRAUMA_PER_HIT: f32 = 0.3;
+
+// Moves the camera focus towards its target, then keeps the visible area inside the level floor.
+#[allow(clippy::type_complexity)]
+pub fn camera_follow_system(
+    time: Res<Time>,
+    target: Query<&Transform, (With<CameraTarget>, Without<GameCamera>)>,
+    mut camera: Query<(&mut GameCamera, &mut Transform, &OrthographicProjection)>,
+    level: Query<(&Transform, &Sprite), (With<LevelFloor>, Without<GameCamera>)>,
+) {
+    let Ok(target_transform) = target.get_single() else { return };
+    let target_position = target_transform.translation.truncate();
+
+    for (mut game_camera, mut transform, projection) in &mut camera {
+        let desired = match game_camera.dead_zone {
+            Some(dead_zone) => follow_dead_zone(game_camera.focus, target_position, dead_zone),
+            None => target_position,
+        };
+
+        let mut focus = if game_camera.smoothing > 0. {
+            // Exponential smoothing keeps the catch-up speed independent of the frame rate.
+            let t = 1. - (-game_camera.smoothing * time.delta_seconds()).exp();
+            game_camera.focus.lerp(desired, t)
+        } else {
+            desired
+        };
+
+        if let Ok((level_transform, level_sprite)) = level.get_single() {
+            if let Some(level_size) = level_sprite.custom_size {
+                focus = clamp_to_bounds(
+                    focus,
+                    projection.area.half_size(),
+                    level_transform.translation.truncate(),
+                    level_size / 2.,
+                );
+            }
+        }
+
+        game_camera.focus = focus;
+        transform.translation.x = focus.x;
+        transform.translation.y = focus.y;
+    }
+}
+
+// Offsets the camera by its current shake; must run after the follow system has reset the translation.
+pub fn camera_shake_system(
+    time: Res<Time>,
+    settings: Res<Settings>,
+    mut camera: Query<(&mut CameraShake, &mut Transform)>,
+) {
+    let elapsed = time.elapsed_seconds();
+
+    for (mut shake, mut transform) in &mut camera {
+        if shake.trauma <= 0. {
+            continue;
+        }
+
+        if settings.screen_shake {
+            let strength = shake.max_offset * shake.trauma * shake.trauma;
+            // Cheap noise; summing incommensurate frequencies avoids a visibly regular wobble.
+            transform.translation.x += strength * ((elapsed * 47.).sin() + (elapsed * 83.).sin()) / 2.;
+            transform.translation.y += strength * ((elapsed * 59.).cos() + (elapsed * 71.).sin()) / 2.;
+        }
+
+        shake.trauma = (shake.trauma - shake.decay * time.delta_seconds()).max(0.);
+    }
+}
+
+pub fn camera_shake_on_hit_system(
+    mut damage_events: EventReader<DamageEvent>,
+    targets: Query<(), With<CameraTarget>>,
+    mut camera: Query<&mut CameraShake>,
+) {
+    for damage in damage_events.iter() {
+        if targets.contains(damage.target) {
+            for mut shake in &mut camera {
+                shake.add_trauma(TRAUMA_PER_HIT);
+            }
+        }
+    }
+}
+
+pub fn camera_zoom_system(
+    settings: Res<Settings>,
+    mut camera: Query<&mut OrthographicProjection, With<GameCamera>>,
+) {
+    if !settings.is_changed() {
+        return;
+    }
+
+    for mut projection in &mut camera {
+        projection.scale = settings.camera_zoom;
+    }
+}
+
+// The projection only knows its area once the camera has something to render to, which headless it never does.
+pub fn view_extents_system(
+    camera: Query<(&Camera, &OrthographicProjection), With<GameCamera>>,
+    mut view_extents: ResMut<ViewExtents>,
+) {
+    for (camera, projection) in &camera {
+        if camera.logical_viewport_size().is_none() {
+            continue;
+        }
+
+        let extents = ViewExtents(projection.area.half_size());
+        if *view_extents != extents {
+            *view_extents = extents;
+        }
+    }
+}
+
+// Returns the focus point needed to keep the target within the dead zone box around the current focus.
+fn follow_dead_zone(focus: Vec2, target: Vec2, dead_zone: Vec2) -> Vec2 {
+    let offset = target - focus;
+    let excess = offset - offset.clamp(-dead_zone, dead_zone);
+
+    focus + excess
+}
+
+// Clamps the focus so a view with the given half size stays within the bounds,
+// centering on an axis where the view is larger than the bounds themselves.
+fn clamp_to_bounds(focus: Vec2, half_view: Vec2, bounds_center: Vec2, bounds_half: Vec2) -> Vec2 {
+    let clamp_axis = |focus: f32, half_view: f32, center: f32, half: f32| {
+        if half_view >= half {
+            center
+        } else {
+            focus.clamp(center - half + half_view, center + half - half_view)
+        }
+    };
+
+    Vec2::new(
+        clamp_axis(focus.x, half_view.x, bounds_center.x, bounds_half.x),
+        clamp_axis(focus.y, half_view.y, bounds_center.y, bounds_half.y),
+    )
+}
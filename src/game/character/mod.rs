@@ -0,0 +1,52 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::game::passive::component::PassiveKind;
+use crate::game::weapon::component::WeaponKind;
+
+// The character played unless another one is picked; `assets/data/game.characters.ron` always has to define it.
+pub const DEFAULT_CHARACTER: &str = "gabe";
+
+// Defined in `assets/data/game.characters.ron`.
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+pub struct Character {
+    pub id: String,
+    pub name: String,
+    pub sprite_sheet: String,
+    // Size of a single frame and the number of frames in the (single row) sprite sheet.
+    pub sprite_size: Vec2,
+    pub sprite_columns: usize,
+    pub tint: Color,
+    pub max_health: f32,
+    pub velocity: f32,
+    pub starting_weapon: WeaponKind,
+    #[serde(default)]
+    pub passive: Option<PassiveKind>,
+    // Gold needed to unlock the character in the character select screen; 0 is unlocked from the start.
+    #[serde(default)]
+    pub unlock_cost: u32,
+}
+
+impl Character {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.sprite_columns == 0 || self.sprite_size.min_element() <= 0. {
+            return Err(format!("character {} has an empty sprite sheet", self.id));
+        }
+
+        if self.max_health <= 0. || self.velocity <= 0. {
+            return Err(format!("character {} needs a positive max health and velocity", self.id));
+        }
+
+        Ok(())
+    }
+}
+
+// The id of the character the next run is played with.
+#[derive(Resource)]
+pub struct SelectedCharacter(pub String);
+
+impl Default for SelectedCharacter {
+    fn default() -> Self {
+        SelectedCharacter(DEFAULT_CHARACTER.to_string())
+    }
+}
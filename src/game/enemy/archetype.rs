@@ -0,0 +1,48 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+
+// Defined in `assets/data/game.enemies.ron`.
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+pub struct EnemyArchetype {
+    pub id: String,
+    pub sprite_sheet: String,
+    pub sprite_size: Vec2,
+    pub sprite_columns: usize,
+    pub tint: Color,
+    pub scale: f32,
+    pub max_health: f32,
+    pub velocity: f32,
+    pub contact_damage: f32,
+    // Fraction of incoming knockback and hit-stun ignored; 1.0 can't be pushed around at all.
+    pub knockback_resistance: f32,
+    // The drop table in `assets/data/game.loot.ron` rolled when the enemy dies.
+    pub drops: String,
+    // Given to the player for the kill.
+    #[serde(default)]
+    pub experience: u32,
+    // Bosses only show up where a level's `boss` asks for them, never in the enemy ring.
+    #[serde(default)]
+    pub boss: bool,
+}
+
+impl EnemyArchetype {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.sprite_columns == 0 || self.sprite_size.min_element() <= 0. {
+            return Err(format!("enemy {} has an empty sprite sheet", self.id));
+        }
+
+        if self.scale <= 0. || self.max_health <= 0. {
+            return Err(format!("enemy {} needs a positive scale and max health", self.id));
+        }
+
+        if self.velocity < 0. || self.contact_damage < 0. {
+            return Err(format!("enemy {} has a negative velocity or contact damage", self.id));
+        }
+
+        if !(0. ..=1.).contains(&self.knockback_resistance) {
+            return Err(format!("enemy {} has a knockback resistance outside 0-1", self.id));
+        }
+
+        Ok(())
+    }
+}
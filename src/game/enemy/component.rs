@@ -0,0 +1,36 @@
+use bevy::prelude::*;
+
+use crate::game::feedback::component::HitFlash;
+use crate::game::health::component::Health;
+use crate::game::movement::component::{Collidable, Knockback, KnockbackResistance, Movable, MoveStep};
+use crate::game::pool::resource::PoolKind;
+use crate::game::sprite::component::{AnimationTimer, BaseColor, FacingDirection, Layered};
+use crate::game::stats::component::Stats;
+use crate::game::status::component::StatusEffects;
+
+// Tag interface for all enemies.
+#[derive(Component)]
+pub struct Enemy;
+
+impl PoolKind for Enemy {
+    const NAME: &'static str = "enemy";
+    type Active = (
+        (Enemy, EnemyKind, Movable, MoveStep, Collidable, Layered, FacingDirection, AnimationTimer, BaseColor),
+        (StatusEffects, Stats, Knockback, KnockbackResistance, ContactDamage, Health, HitFlash),
+    );
+}
+
+// Tag interface for marking enemies that will not follow the regular movement pattern of always pathing towards the player.
+#[derive(Component)]
+pub struct IrregularEnemy;
+
+// The id of the archetype an enemy was spawned from.
+#[derive(Component)]
+pub struct EnemyKind(pub String);
+
+// Damage dealt to the player on touch, at most once per `cooldown`.
+#[derive(Component)]
+pub struct ContactDamage {
+    pub damage: f32,
+    pub cooldown: Timer,
+}
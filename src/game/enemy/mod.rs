@@ -0,0 +1,3 @@
+pub mod archetype;
+pub mod component;
+pub mod system;
@@ -0,0 +1,243 @@
+use bevy::prelude::*;
+use bevy::sprite::collide_aabb::collide;
+use rand::Rng;
+
+use crate::audio::event::{Sfx, SfxEvent};
+use crate::data::resource::GameData;
+use crate::game::GameEntity;
+use crate::game::enemy::archetype::EnemyArchetype;
+use crate::game::enemy::component::{ContactDamage, Enemy, EnemyKind, IrregularEnemy};
+use crate::game::health::component::Health;
+use crate::game::health::event::DamageEvent;
+use crate::game::level::SelectedLevel;
+use crate::game::movement::component::{Collidable, Knockback, KnockbackResistance, Movable, MoveStep};
+use crate::game::movement::event::KnockbackEvent;
+use crate::game::pathfinding::resource::FlowField;
+use crate::game::pickup::component::Pickup;
+use crate::game::pickup::system::spawn_pickup_at;
+use crate::game::pool::resource::EntityPool;
+use crate::game::player::component::{Experience, PlayerControlled};
+use crate::game::simulation::resource::{SimulationRng, SimulationTick};
+use crate::game::sprite::component::{AnimationTimer, BaseColor, FacingDirection, Layered};
+use crate::game::stats::component::{Stat, Stats};
+use crate::game::status::component::StatusEffects;
+use crate::loading::resource::GameAssets;
+
+// How hard, and for how long, enemies bounce off the player after touching them.
+const CONTACT_KNOCKBACK_FORCE: f32 = 400.;
+const CONTACT_HIT_STUN_SECS: f32 = 0.3;
+
+// How far inside the edge of the level a boss is kept when the player is standing close to it.
+const BOSS_SPAWN_MARGIN: f32 = 64.;
+
+// Brings in the selected level's boss once the run has gone on long enough, somewhere around the player but
+// never outside the level.
+#[allow(clippy::too_many_arguments)]
+pub fn boss_spawn_system(
+    mut commands: Commands,
+    game_assets: Res<GameAssets>,
+    game_data: Res<GameData>,
+    selected_level: Res<SelectedLevel>,
+    tick: Res<SimulationTick>,
+    player: Query<&Transform, With<PlayerControlled>>,
+    mut enemy_pool: ResMut<EntityPool<Enemy>>,
+    mut rng: ResMut<SimulationRng>,
+) {
+    let Some(level) = game_data.level(&selected_level.0) else { return };
+    let Some(boss) = level.boss.as_ref().filter(|boss| boss.tick() == tick.0) else { return };
+    let (Some(archetype), Ok(player_transform)) = (game_data.archetype(&boss.archetype), player.get_single()) else {
+        return;
+    };
+
+    let angle = rng.gen_range(0. ..std::f32::consts::TAU);
+    let position = player_transform.translation.truncate() + Vec2::from_angle(angle) * boss.distance;
+    let half_extents = (level.floor_size / 2. - BOSS_SPAWN_MARGIN).max(Vec2::ZERO);
+    let position = position.clamp(-half_extents, half_extents);
+    spawn_enemy_at(&mut commands, &game_assets, &mut enemy_pool, archetype, position.extend(0.));
+}
+
+// Returns enemies that have run out of health to the pool, leaving behind whatever their drop table rolls and
+// giving the player the experience they're worth.
+#[allow(clippy::too_many_arguments)]
+pub fn enemy_death_system(
+    mut commands: Commands,
+    game_data: Res<GameData>,
+    enemies: Query<(Entity, &Health, &Transform, &EnemyKind), With<Enemy>>,
+    mut player: Query<&mut Experience, With<PlayerControlled>>,
+    mut enemy_pool: ResMut<EntityPool<Enemy>>,
+    mut pickup_pool: ResMut<EntityPool<Pickup>>,
+    mut sfx_events: EventWriter<SfxEvent>,
+    mut rng: ResMut<SimulationRng>,
+) {
+    for (entity, health, transform, kind) in &enemies {
+        if health.current <= 0. {
+            sfx_events.send(SfxEvent(Sfx::Death));
+            let archetype = game_data.archetype(&kind.0);
+            if let (Some(archetype), Ok(mut experience)) = (archetype, player.get_single_mut()) {
+                experience.points += archetype.experience;
+            }
+
+            let drop = archetype.and_then(|archetype| game_data.drop_table(&archetype.drops).roll(&mut rng.0));
+            if let Some(pickup) = drop {
+                // Scatter drops a little so a pile of deaths doesn't stack every pickup on one spot.
+                let scatter = Vec3::new(rng.gen_range(-8. ..8.), rng.gen_range(-8. ..8.), 0.);
+                spawn_pickup_at(&mut commands, &mut pickup_pool, pickup, transform.translation + scatter);
+            }
+            enemy_pool.release(&mut commands, entity);
+        }
+    }
+}
+
+// Makes use of the Movable struct to point the enemy towards the player character, following the flow field
+// around any obstacles in the way. Enemies still reeling from a hit stop chasing until the hit-stun wears off.
+#[allow(clippy::type_complexity)]
+pub fn regular_enemy_movement(
+    flow_field: Res<FlowField>,
+    player_character: Query<&Transform, (With<PlayerControlled>, Without<Enemy>)>,
+    mut enemies: Query<(&mut Movable, &Transform, Option<&Knockback>), (With<Enemy>, Without<IrregularEnemy>)>,
+) {
+    let Ok(player_transform) = player_character.get_single() else { return };
+
+    for (mut enemy_movable, enemy_transform, knockback) in &mut enemies {
+        if knockback.is_some_and(Knockback::is_stunned) {
+            enemy_movable.direction = Vec2::ZERO;
+            continue;
+        }
+
+        let enemy_position = enemy_transform.translation.truncate();
+        enemy_movable.direction = flow_field.direction(enemy_position)
+            .unwrap_or(player_transform.translation.truncate() - enemy_position);
+    }
+}
+
+// Damages the player when an enemy touches them, knocking the enemy back so it doesn't just stick to the player.
+#[allow(clippy::type_complexity)]
+pub fn enemy_contact_damage_system(
+    fixed_time: Res<FixedTime>,
+    player: Query<(Entity, &Transform, &Collidable), (With<PlayerControlled>, Without<Enemy>)>,
+    mut enemies: Query<(Entity, &Transform, &Collidable, &mut ContactDamage), With<Enemy>>,
+    mut damage_events: EventWriter<DamageEvent>,
+    mut knockback_events: EventWriter<KnockbackEvent>,
+) {
+    let _span = info_span!("contact collision").entered();
+
+    let Ok((player, player_transform, player_collidable)) = player.get_single() else { return };
+    let mut player_translation = player_transform.translation;
+    player_translation.z = 1.0;
+
+    for (enemy, enemy_transform, enemy_collidable, mut contact_damage) in &mut enemies {
+        contact_damage.cooldown.tick(fixed_time.period);
+        if !contact_damage.cooldown.finished() {
+            continue;
+        }
+
+        let mut enemy_translation = enemy_transform.translation;
+        enemy_translation.z = 1.0;
+
+        if collide(enemy_translation, enemy_collidable.size(), player_translation, player_collidable.size()).is_some() {
+            damage_events.send(DamageEvent { target: player, amount: contact_damage.damage });
+            knockback_events.send(KnockbackEvent {
+                target: enemy,
+                source: player_translation.truncate(),
+                force: CONTACT_KNOCKBACK_FORCE,
+                stun_secs: CONTACT_HIT_STUN_SECS,
+            });
+            contact_damage.cooldown.reset();
+        }
+    }
+}
+
+pub fn spawn_enemy_at(
+    commands: &mut Commands,
+    game_assets: &GameAssets,
+    enemy_pool: &mut EntityPool<Enemy>,
+    archetype: &EnemyArchetype,
+    vec3_translation: Vec3
+) {
+    // Split in two as bundles only go up to 15 components.
+    enemy_pool.spawn(
+        commands,
+        (
+            (
+                SpriteSheetBundle {
+                    texture_atlas: game_assets.enemy_atlases[&archetype.id].clone(),
+                    sprite: TextureAtlasSprite {
+                        color: archetype.tint,
+                        ..default()
+                    },
+                    transform: Transform::from_scale(Vec3::splat(archetype.scale))
+                        .with_translation(vec3_translation),
+                    ..default()
+                },
+                Movable {
+                    velocity: archetype.velocity,
+                    direction: Vec2::ZERO,
+                },
+                MoveStep::default(),
+                enemy_collider(archetype),
+                GameEntity,
+                FacingDirection::East,
+                Layered,
+                Enemy,
+                EnemyKind(archetype.id.clone()),
+                BaseColor(archetype.tint),
+                AnimationTimer(Timer::from_seconds(0.1, TimerMode::Repeating)),
+            ),
+            (
+                StatusEffects::default(),
+                Stats::new([(Stat::MaxHealth, archetype.max_health), (Stat::MoveSpeed, archetype.velocity), (Stat::Damage, 1.)]),
+                Knockback::default(),
+                KnockbackResistance(archetype.knockback_resistance),
+                ContactDamage {
+                    damage: archetype.contact_damage,
+                    cooldown: Timer::from_seconds(0.5, TimerMode::Once),
+                },
+                Health {
+                    max: archetype.max_health,
+                    current: archetype.max_health
+                }
+            )
+        )
+    );
+}
+
+// Brings living enemies in line with their archetype after `assets/data/game.enemies.ron` changes.
+// Health isn't touched beyond what a changed max health does to it; see `stats_sync_system`.
+#[allow(clippy::type_complexity)]
+pub fn enemy_archetype_reload_system(
+    game_data: Res<GameData>,
+    game_assets: Res<GameAssets>,
+    mut enemies: Query<(
+        &EnemyKind,
+        &mut Stats,
+        &mut KnockbackResistance,
+        &mut ContactDamage,
+        &mut BaseColor,
+        &mut Transform,
+        &mut Collidable,
+        &mut Handle<TextureAtlas>,
+    )>,
+) {
+    for (kind, mut stats, mut knockback_resistance, mut contact_damage, mut base_color, mut transform, mut collidable, mut atlas) in &mut enemies {
+        let Some(archetype) = game_data.archetype(&kind.0) else {
+            warn!("Enemy archetype {} no longer exists; leaving its enemies as they are", kind.0);
+            continue;
+        };
+
+        stats.set_base(Stat::MaxHealth, archetype.max_health);
+        stats.set_base(Stat::MoveSpeed, archetype.velocity);
+        knockback_resistance.0 = archetype.knockback_resistance;
+        contact_damage.damage = archetype.contact_damage;
+        base_color.0 = archetype.tint;
+        transform.scale = Vec3::splat(archetype.scale);
+        *collidable = enemy_collider(archetype);
+        *atlas = game_assets.enemy_atlases[&archetype.id].clone();
+    }
+}
+
+fn enemy_collider(archetype: &EnemyArchetype) -> Collidable {
+    Collidable {
+        // Collider sizes are tuned for the default sprite scale of 2.
+        size: Transform::from_scale(Vec3::new(13. * archetype.scale, 10. * archetype.scale, 1.))
+    }
+}
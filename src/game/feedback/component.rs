@@ -0,0 +1,22 @@
+use bevy::prelude::*;
+
+use crate::game::pool::resource::PoolKind;
+
+// Briefly overrides the sprite colour after being hit; see `sprite_color_system`.
+#[derive(Component)]
+pub struct HitFlash {
+    pub color: Color,
+    pub remaining_secs: f32,
+}
+
+// A pooled floating damage number; hidden and returned to its pool once `lifetime` finishes.
+#[derive(Component)]
+pub struct DamageNumber {
+    pub lifetime: Timer,
+}
+
+// Damage numbers are only ever hidden, so their text can be rewritten in place without allocating.
+impl PoolKind for DamageNumber {
+    const NAME: &'static str = "damage number";
+    type Active = ();
+}
@@ -0,0 +1,154 @@
+use std::fmt::Write;
+
+use bevy::ecs::query::Has;
+use bevy::prelude::*;
+use bevy::text::Text2dBounds;
+
+use crate::game::GameEntity;
+use crate::game::feedback::component::{DamageNumber, HitFlash};
+use crate::game::health::event::DamageEvent;
+use crate::game::player::component::PlayerControlled;
+use crate::game::pool::resource::EntityPool;
+use crate::game::sprite::component::BaseColor;
+use crate::loading::resource::GameAssets;
+use crate::settings::resource::Settings;
+
+// Enough for a busy screen; the pool only grows past this when it runs dry.
+const DAMAGE_NUMBER_POOL_SIZE: usize = 256;
+const DAMAGE_NUMBER_LIFETIME_SECS: f32 = 0.7;
+const DAMAGE_NUMBER_RISE_PER_SECOND: f32 = 40.;
+const HIT_FLASH_SECS: f32 = 0.1;
+
+// Warms the damage number pool up with hidden numbers.
+pub fn setup_damage_number_pool(
+    mut commands: Commands,
+    game_assets: Res<GameAssets>,
+    mut pool: ResMut<EntityPool<DamageNumber>>,
+) {
+    for _ in 0..DAMAGE_NUMBER_POOL_SIZE {
+        let entity = spawn_damage_number(&mut commands, game_assets.font.clone(), Vec3::ZERO, Visibility::Hidden);
+        pool.add_free(entity);
+    }
+}
+
+fn spawn_damage_number(commands: &mut Commands, font: Handle<Font>, translation: Vec3, visibility: Visibility) -> Entity {
+    commands.spawn(
+        (
+            Text2dBundle {
+                text: Text::from_section(
+                    // Reserve some capacity up front so writing the damage in never allocates.
+                    String::with_capacity(8),
+                    damage_number_style(font),
+                ).with_alignment(TextAlignment::Center),
+                text_2d_bounds: Text2dBounds::UNBOUNDED,
+                transform: Transform::from_translation(translation),
+                visibility,
+                ..default()
+            },
+            DamageNumber {
+                lifetime: Timer::from_seconds(DAMAGE_NUMBER_LIFETIME_SECS, TimerMode::Once),
+            },
+            GameEntity,
+        )
+    ).id()
+}
+
+fn damage_number_style(font: Handle<Font>) -> TextStyle {
+    TextStyle {
+        font,
+        font_size: 16.,
+        color: Color::WHITE,
+    }
+}
+
+// Shows a damage number above everything that took damage, reusing hidden numbers from the pool.
+pub fn damage_number_spawn_system(
+    mut commands: Commands,
+    settings: Res<Settings>,
+    mut damage_events: EventReader<DamageEvent>,
+    game_assets: Res<GameAssets>,
+    mut pool: ResMut<EntityPool<DamageNumber>>,
+    targets: Query<&Transform, Without<DamageNumber>>,
+    mut numbers: Query<(&mut Text, &mut Transform, &mut Visibility, &mut DamageNumber)>,
+) {
+    if !settings.damage_numbers {
+        damage_events.clear();
+        return;
+    }
+
+    for damage in damage_events.iter() {
+        let Ok(target_transform) = targets.get(damage.target) else { continue };
+        let position = (target_transform.translation.truncate() + Vec2::new(0., 20.)).extend(10.);
+
+        let Some(entity) = pool.acquire() else {
+            // The pool ran dry; this is the only path that allocates.
+            let entity = spawn_damage_number(&mut commands, game_assets.font.clone(), position, Visibility::Visible);
+            commands.entity(entity).insert(Text::from_section(
+                format!("{}", damage.amount.round()),
+                damage_number_style(game_assets.font.clone()),
+            ).with_alignment(TextAlignment::Center));
+            continue;
+        };
+
+        let Ok((mut text, mut transform, mut visibility, mut damage_number)) = numbers.get_mut(entity) else { continue };
+        let section = &mut text.sections[0];
+        section.value.clear();
+        let _ = write!(section.value, "{}", damage.amount.round());
+        section.style.color = Color::WHITE;
+
+        transform.translation = position;
+        *visibility = Visibility::Visible;
+        damage_number.lifetime.reset();
+    }
+}
+
+// Floats damage numbers upwards while fading them out, returning them to the pool once gone.
+pub fn damage_number_float_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut pool: ResMut<EntityPool<DamageNumber>>,
+    mut numbers: Query<(Entity, &mut Text, &mut Transform, &Visibility, &mut DamageNumber)>,
+) {
+    for (entity, mut text, mut transform, visibility, mut damage_number) in &mut numbers {
+        if *visibility == Visibility::Hidden {
+            continue;
+        }
+
+        if damage_number.lifetime.tick(time.delta()).finished() {
+            pool.release(&mut commands, entity);
+            continue;
+        }
+
+        transform.translation.y += DAMAGE_NUMBER_RISE_PER_SECOND * time.delta_seconds();
+        text.sections[0].style.color.set_a(damage_number.lifetime.percent_left());
+    }
+}
+
+// Flashes anything with a sprite colour when hit: red for the player, white for everything else.
+pub fn hit_flash_on_damage_system(
+    mut commands: Commands,
+    settings: Res<Settings>,
+    mut damage_events: EventReader<DamageEvent>,
+    flashable: Query<Has<PlayerControlled>, With<BaseColor>>,
+) {
+    if !settings.hit_flash {
+        damage_events.clear();
+        return;
+    }
+
+    for damage in damage_events.iter() {
+        let Ok(is_player) = flashable.get(damage.target) else { continue };
+        let color = if is_player { Color::rgb(1., 0.2, 0.2) } else { Color::WHITE };
+
+        commands.entity(damage.target).insert(HitFlash { color, remaining_secs: HIT_FLASH_SECS });
+    }
+}
+
+pub fn hit_flash_tick_system(time: Res<Time>, mut flashes: Query<&mut HitFlash>) {
+    for mut flash in &mut flashes {
+        // Only touch flashes that are still running, so finished ones stop triggering change detection.
+        if flash.remaining_secs > 0. {
+            flash.remaining_secs = (flash.remaining_secs - time.delta_seconds()).max(0.);
+        }
+    }
+}
@@ -7,4 +7,8 @@ pub struct Health {
 }
 
 #[derive(Component)]
-pub struct PlayerHealthIndicator;
\ No newline at end of file
+pub struct PlayerHealthIndicator;
+
+// Takes no damage at all; the debug console's god mode.
+#[derive(Component)]
+pub struct Invulnerable;
@@ -0,0 +1,8 @@
+use bevy::prelude::*;
+
+// Sent whenever something should lose health; the health system is the only place `Health.current` is reduced.
+#[derive(Event)]
+pub struct DamageEvent {
+    pub target: Entity,
+    pub amount: f32,
+}
@@ -1,3 +1,4 @@
 pub mod component;
+pub mod event;
 
-pub mod system;
\ No newline at end of file
+pub mod system;
@@ -1,17 +1,29 @@
 use bevy::prelude::*;
 use super::component::PlayerHealthIndicator;
 use crate::game::player::component::PlayerControlled;
-use crate::game::health::component::Health;
+use crate::game::health::component::{Health, Invulnerable};
+use crate::game::health::event::DamageEvent;
 
 pub fn player_health_indicator_update(
     mut player_health_indicator: Query<&mut Sprite, With<PlayerHealthIndicator>>,
     player_health: Query<&Health, With<PlayerControlled>>,
 ) {
     let player_health = player_health.single();
-    // info!("Player health: {}", player_health.current);
 
     for mut health_sprite in &mut player_health_indicator {
         let health_decimal = player_health.current / player_health.max;
         health_sprite.custom_size = Some(Vec2::new(20. * health_decimal, 5.))
     }
-}
\ No newline at end of file
+}
+
+// Applies all damage sent this frame, never letting health drop below 0.
+pub fn apply_damage_system(
+    mut damage_events: EventReader<DamageEvent>,
+    mut damageable: Query<&mut Health, Without<Invulnerable>>,
+) {
+    for damage in damage_events.iter() {
+        if let Ok(mut health) = damageable.get_mut(damage.target) {
+            health.current = (health.current - damage.amount).max(0.);
+        }
+    }
+}
@@ -1 +1,116 @@
-pub mod component;
\ No newline at end of file
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::audio::event::MusicTrack;
+use crate::game::simulation::SIMULATION_TICK_SECS;
+
+pub mod component;
+
+// The level played unless another one is picked; `assets/data/game.levels.ron` always has to define it.
+pub const DEFAULT_LEVEL: &str = "default";
+
+// Defined in `assets/data/game.levels.ron`.
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+pub struct LevelDefinition {
+    pub name: String,
+    pub floor_size: Vec2,
+    pub floor_color: Color,
+    pub trees: TreeRow,
+    pub enemy_ring: EnemyRing,
+    #[serde(default)]
+    pub props: Vec<PropPlacement>,
+    #[serde(default)]
+    pub boss: Option<BossSpawn>,
+    // Played instead of the regular gameplay music; the boss track still takes over while a boss is alive.
+    #[serde(default)]
+    pub music: Option<MusicTrack>,
+}
+
+// A boss that joins the run once it has gone on for `after_secs`, `distance` away from the player.
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+pub struct BossSpawn {
+    pub archetype: String,
+    pub after_secs: f32,
+    pub distance: f32,
+}
+
+impl BossSpawn {
+    // The simulation tick the boss spawns on; never the first, so the player is there to measure `distance` from.
+    pub fn tick(&self) -> u64 {
+        (self.after_secs / SIMULATION_TICK_SECS).round().max(1.) as u64
+    }
+}
+
+// A destructible prop placed by hand.
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+pub struct PropPlacement {
+    pub archetype: String,
+    pub position: Vec2,
+}
+
+// A wave of trees along the x axis: one every `spacing` units between `from_x` and `to_x`, at `cos(x) * amplitude`.
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+pub struct TreeRow {
+    pub from_x: i32,
+    pub to_x: i32,
+    pub spacing: i32,
+    pub amplitude: f32,
+}
+
+impl TreeRow {
+    pub fn positions(&self) -> impl Iterator<Item = Vec2> + '_ {
+        (self.from_x..=self.to_x)
+            .filter(|x| x.abs() % self.spacing == 0)
+            .map(|x| Vec2::new(x as f32, (x as f32).cos() * self.amplitude))
+    }
+}
+
+// Enemies spawned in a ring around the player when the run starts.
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+pub struct EnemyRing {
+    pub radius: f32,
+    pub every_degrees: i32,
+    pub archetype: String,
+    // Every so often an elite spawns in place of a regular enemy.
+    pub elite_every_degrees: i32,
+    pub elite_archetype: String,
+}
+
+impl EnemyRing {
+    pub fn spawns(&self) -> impl Iterator<Item = (&str, Vec2)> + '_ {
+        (1..360).filter(|angle| angle % self.every_degrees == 0).map(|angle| {
+            let archetype = if angle % self.elite_every_degrees == 0 { &self.elite_archetype } else { &self.archetype };
+            let angle = angle as f32;
+
+            (archetype.as_str(), Vec2::new(self.radius * angle.cos(), self.radius * angle.sin()))
+        })
+    }
+}
+
+impl LevelDefinition {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.floor_size.min_element() <= 0. {
+            return Err(format!("level {} needs a floor", self.name));
+        }
+
+        if self.trees.spacing <= 0 || self.enemy_ring.every_degrees <= 0 || self.enemy_ring.elite_every_degrees <= 0 {
+            return Err(format!("level {} needs positive tree spacing and enemy ring intervals", self.name));
+        }
+
+        if self.boss.as_ref().is_some_and(|boss| boss.after_secs < 0. || boss.distance <= 0.) {
+            return Err(format!("level {} needs a boss spawn time of at least 0 and a positive distance", self.name));
+        }
+
+        Ok(())
+    }
+}
+
+// The level the next run is played on.
+#[derive(Resource)]
+pub struct SelectedLevel(pub String);
+
+impl Default for SelectedLevel {
+    fn default() -> Self {
+        SelectedLevel(DEFAULT_LEVEL.to_string())
+    }
+}
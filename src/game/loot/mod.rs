@@ -0,0 +1,72 @@
+use rand::Rng;
+use serde::Deserialize;
+
+use crate::game::pickup::component::Pickup;
+
+// What something leaves behind when it's destroyed: at most one of `drops`, picked by weight,
+// or nothing at all with a weight of `nothing`.
+#[derive(Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct DropTable {
+    #[serde(default)]
+    pub nothing: u32,
+    pub drops: Vec<Drop>,
+}
+
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+pub struct Drop {
+    pub pickup: Pickup,
+    pub weight: u32,
+}
+
+impl DropTable {
+    pub fn roll(&self, rng: &mut impl Rng) -> Option<Pickup> {
+        let total: u32 = self.nothing + self.drops.iter().map(|drop| drop.weight).sum::<u32>();
+        if total == 0 {
+            return None;
+        }
+
+        let mut roll = rng.gen_range(0..total);
+        for drop in &self.drops {
+            if roll < drop.weight {
+                return Some(drop.pickup);
+            }
+            roll -= drop.weight;
+        }
+
+        None
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        for drop in &self.drops {
+            drop.pickup.validate()?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    use super::*;
+
+    #[test]
+    fn drops_by_weight() {
+        let table = DropTable {
+            nothing: 2,
+            drops: vec![
+                Drop { pickup: Pickup::Gold(1), weight: 1 },
+                Drop { pickup: Pickup::Magnet, weight: 1 },
+            ],
+        };
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        let rolls: Vec<_> = (0..4000).map(|_| table.roll(&mut rng)).collect();
+        let count = |pickup| rolls.iter().filter(|roll| **roll == pickup).count();
+
+        assert!((1800..2200).contains(&count(None)));
+        assert!((800..1200).contains(&count(Some(Pickup::Gold(1)))));
+        assert!((800..1200).contains(&count(Some(Pickup::Magnet))));
+    }
+}
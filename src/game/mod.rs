@@ -2,21 +2,85 @@ use bevy::prelude::*;
 use bevy::sprite::Anchor;
 
 use crate::AppState;
-use player::system::keyboard_input_system;
-use sprite::system::{ animate_sprite, layering_system };
-use movement::system::entity_movement_system;
+use crate::audio::event::SfxEvent;
+use crate::data::DataReloadSet;
+use crate::data::resource::GameData;
+use player::PlayerInputSet;
+use player::resource::PlayerInput;
+use player::system::{keyboard_input_system, player_control_system};
+use sprite::system::{ animate_sprite, layering_system, sprite_color_system };
+use movement::{movement_systems, MovementSet};
+use movement::resource::StaticColliders;
+use level::{SelectedLevel, DEFAULT_LEVEL};
 use level::component::LevelFloor;
+use camera::CameraPlugin;
+use camera::component::CameraTarget;
+use crate::game::enemy::component::Enemy;
+use crate::game::enemy::system::{
+    boss_spawn_system, enemy_archetype_reload_system, enemy_contact_damage_system, enemy_death_system,
+    regular_enemy_movement, spawn_enemy_at,
+};
+use crate::game::feedback::system::*;
+use crate::game::game_state::GameState;
 use crate::game::health::component::{Health, PlayerHealthIndicator};
-use crate::game::movement::component::{Collidable, Movable};
-use crate::game::player::component::PlayerControlled;
-use crate::game::sprite::component::{AnimationTimer, FacingDirection, Layered};
+use crate::game::health::event::DamageEvent;
+use crate::game::health::system::{apply_damage_system, player_health_indicator_update};
+use crate::game::movement::component::{Collidable, Movable, MoveStep};
+use crate::game::movement::event::KnockbackEvent;
+use crate::game::movement::system::apply_knockback_system;
+use crate::game::character::{SelectedCharacter, DEFAULT_CHARACTER};
+use crate::game::passive::component::Passive;
+use crate::game::passive::system::{might_system, regeneration_system};
+use crate::game::pathfinding::resource::FlowField;
+use crate::game::pathfinding::system::flow_field_system;
+use crate::game::pickup::system::{
+    evolution_offer_system, evolution_offer_text_system, level_up_system, magnetised_pickup_system,
+    pickup_collection_system, setup_evolution_offer_text,
+};
+use crate::game::player::component::{Experience, PlayerControlled};
+use crate::game::pool::{PoolPlugin, PoolResetSet};
+use crate::game::pool::resource::EntityPool;
+use crate::game::prop::system::{prop_break_system, prop_breaking_system, spawn_prop_at};
+use crate::game::simulation::{add_simulation_event, SimulationPlugin, SimulationTickSet};
+use crate::loading::resource::GameAssets;
+use crate::replay::resource::ReplayPlayback;
+use crate::save::resource::SaveData;
+use crate::game::stats::component::{ModifierSource, ModifierValue, Stat, StatModifier, Stats};
+use crate::game::stats::system::{stats_sync_system, status_stat_modifier_system};
+use crate::game::status::component::StatusEffects;
+use crate::game::status::event::ApplyStatusEvent;
+use crate::game::status::system::{apply_status_event_system, status_effect_tick_system};
+use crate::game::targeting::TargetingSet;
+use crate::game::targeting::resource::EnemyIndex;
+use crate::game::targeting::system::enemy_index_system;
+use crate::game::weapon::component::Weapon;
+use crate::game::weapon::system::{
+    damage_zone_system, homing_system, orbit_system, projectile_system, weapon_fire_system, weapon_stats_reload_system,
+};
+use crate::shop::upgrade::ShopUpgrade;
+use crate::game::sprite::component::{AnimationTimer, BaseColor, FacingDirection, Layered};
 
+pub mod camera;
+pub mod character;
+pub mod enemy;
+pub mod feedback;
 pub mod game_state;
 pub mod level;
+pub mod loot;
 pub mod movement;
+pub mod passive;
+pub mod pathfinding;
+pub mod pickup;
 pub mod player;
+pub mod pool;
+pub mod prop;
+pub mod simulation;
 pub mod sprite;
+pub mod stats;
+pub mod status;
+pub mod targeting;
 pub mod health;
+pub mod weapon;
 
 
 
@@ -27,30 +91,92 @@ pub struct GamePlugin;
 
 impl Plugin for GamePlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(AppState::GamePlaying), game_setup)
-            .add_systems(Update, (animate_sprite, layering_system).run_if(in_state(AppState::GamePlaying)))
-            .add_systems(Update, (entity_movement_system).run_if(in_state(AppState::GamePlaying)))
-            .add_systems(Update, keyboard_input_system.run_if(in_state(AppState::GamePlaying)))
-            .add_systems(OnExit(AppState::MainMenu), despawn_screen::<GameEntity>);
+        // Contact damage and pickups send these after movement, for systems that already ran this tick.
+        add_simulation_event::<ApplyStatusEvent>(app);
+        add_simulation_event::<KnockbackEvent>(app);
+
+        app.add_event::<DamageEvent>()
+            // Gameplay sends sound effects whether or not the audio plugin is there to play them.
+            .add_event::<SfxEvent>()
+            .init_resource::<SelectedCharacter>()
+            .init_resource::<PlayerInput>()
+            .init_resource::<SelectedLevel>()
+            .init_resource::<StaticColliders>()
+            .init_resource::<FlowField>()
+            .init_resource::<EnemyIndex>()
+            .add_state::<GameState>()
+            .add_plugins(CameraPlugin)
+            .add_plugins(PoolPlugin)
+            .add_plugins(SimulationPlugin)
+            .add_systems(OnEnter(AppState::GamePlaying), (game_setup, setup_damage_number_pool, setup_evolution_offer_text).after(PoolResetSet))
+            .configure_set(FixedUpdate, PlayerInputSet.after(SimulationTickSet).run_if(in_state(AppState::GamePlaying)))
+            .add_systems(FixedUpdate, keyboard_input_system.in_set(PlayerInputSet).run_if(not(resource_exists::<ReplayPlayback>())))
+            // Everything that affects the outcome of a run steps in `FixedUpdate` so it plays out the same at any frame rate.
+            .add_systems(FixedUpdate, (
+                player_control_system.before(MovementSet),
+                (regular_enemy_movement, apply_knockback_system).chain().before(MovementSet),
+                movement_systems(),
+                flow_field_system.after(MovementSet),
+                enemy_index_system.in_set(TargetingSet).before(MovementSet),
+                boss_spawn_system.before(MovementSet).before(TargetingSet),
+                animate_sprite.after(MovementSet),
+                enemy_contact_damage_system.after(MovementSet).before(apply_damage_system),
+                (weapon_fire_system, (homing_system, orbit_system), projectile_system, damage_zone_system).chain()
+                    .after(TargetingSet).before(apply_damage_system).before(apply_knockback_system),
+                (regeneration_system, might_system).before(stats_sync_system).before(apply_damage_system),
+                (apply_status_event_system, status_effect_tick_system).chain()
+                    .after(damage_zone_system).before(apply_damage_system).before(MovementSet),
+                (status_stat_modifier_system, stats_sync_system).chain()
+                    .after(status_effect_tick_system).after(might_system).before(MovementSet).before(apply_damage_system),
+                (apply_damage_system, player_health_indicator_update, enemy_death_system).chain(),
+                (prop_break_system, prop_breaking_system).chain().after(apply_damage_system),
+                (magnetised_pickup_system, pickup_collection_system).chain().after(MovementSet).before(apply_damage_system),
+                level_up_system.after(pickup_collection_system).after(enemy_death_system),
+                evolution_offer_system.before(pickup_collection_system).before(weapon_fire_system),
+            ).after(PlayerInputSet).run_if(in_state(AppState::GamePlaying)))
+            // Purely cosmetic systems follow the frame rate.
+            .add_systems(Update, (layering_system, evolution_offer_text_system).run_if(in_state(AppState::GamePlaying)))
+            .add_systems(Update, (damage_number_spawn_system, damage_number_float_system).chain().run_if(in_state(AppState::GamePlaying)))
+            .add_systems(Update, (hit_flash_on_damage_system, hit_flash_tick_system, sprite_color_system).chain().run_if(in_state(AppState::GamePlaying)))
+            // Live entities follow edits to the data files; levels only change from the next run on.
+            .add_systems(Update, (enemy_archetype_reload_system, weapon_stats_reload_system)
+                .after(DataReloadSet)
+                .run_if(in_state(AppState::GamePlaying).and_then(resource_changed::<GameData>())))
+            .add_systems(OnExit(AppState::MainMenu), despawn_screen::<GameEntity>)
+            .add_systems(OnExit(AppState::GamePlaying), (despawn_screen::<GameEntity>, resume_game))
+            // Time, and with it the simulation, only moves while the game is running.
+            .add_systems(OnExit(GameState::Running), |mut time: ResMut<Time>| time.pause())
+            .add_systems(OnEnter(GameState::Running), |mut time: ResMut<Time>| time.unpause());
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn game_setup(
     mut commands: Commands,
-    asset_server: Res<AssetServer>,
-    mut texture_atlases: ResMut<Assets<TextureAtlas>>,
+    game_assets: Res<GameAssets>,
+    game_data: Res<GameData>,
+    save: Res<SaveData>,
+    selected_character: Res<SelectedCharacter>,
+    selected_level: Res<SelectedLevel>,
+    mut enemy_pool: ResMut<EntityPool<Enemy>>,
+    playback: Option<Res<ReplayPlayback>>,
 ) {
-    let texture_handle = asset_server.load("player_character/gabe-idle-run.png");
-    let texture_atlas = TextureAtlas::from_grid(texture_handle, Vec2::new(24.0, 24.0), 7, 1, None, None);
-    let texture_atlas_handle = texture_atlases.add(texture_atlas);
+    let character = game_data.character(&selected_character.0).unwrap_or_else(|| {
+        warn!("Character {} no longer exists; playing {} instead", selected_character.0, DEFAULT_CHARACTER);
+        game_data.character(DEFAULT_CHARACTER).expect("the default character is always defined")
+    });
+    let level = game_data.level(&selected_level.0).unwrap_or_else(|| {
+        warn!("Level {} no longer exists; playing {} instead", selected_level.0, DEFAULT_LEVEL);
+        game_data.level(DEFAULT_LEVEL).expect("the default level is always defined")
+    });
 
     // Spawn and insert the background for the "walkable" level
     commands.spawn(
         (
             SpriteBundle {
                 sprite: Sprite {
-                    color: Color::rgb(0., 128., 0.),
-                    custom_size: Some(Vec2::new(5000., 2000.)),
+                    color: level.floor_color,
+                    custom_size: Some(level.floor_size),
                     ..default()
                 },
                 transform: Transform::from_xyz(0., 0., 0.),
@@ -61,46 +187,67 @@ fn game_setup(
         )
     );
 
-    // Let's just spawn a sine-wave of trees.
-    for x_spawn_coord in -2500i32..2501i32 {
-        if x_spawn_coord.abs() % 50 == 0 {
-            let x_spawn_float = x_spawn_coord as f32;
-            let y_spawn_float = x_spawn_float.cos() * 500.;
-            let z_spawn_float = 0.5 - (y_spawn_float / 2000.);
-            spawn_tree_at(&mut commands, &asset_server, Vec3::new(x_spawn_float, y_spawn_float, z_spawn_float));
+    for position in level.trees.positions() {
+        let z = 0.5 - (position.y / 2000.);
+        spawn_tree_at(&mut commands, &game_assets, position.extend(z));
+    }
+
+    for prop in &level.props {
+        if let Some(archetype) = game_data.prop(&prop.archetype) {
+            let z = 0.5 - (prop.position.y / 2000.);
+            spawn_prop_at(&mut commands, &game_assets, archetype, prop.position.extend(z));
+        }
+    }
 
-            // info!("Spawning tree at: <{},{},{}>", x_spawn_float, y_spawn_float, z_spawn_float);
+    for (archetype, position) in level.enemy_ring.spawns() {
+        if let Some(archetype) = game_data.archetype(archetype) {
+            spawn_enemy_at(&mut commands, &game_assets, &mut enemy_pool, archetype, position.extend(0.));
         }
     }
 
-    let circle_spawn_radius = 700.;
-    let angle_interval = 15f32;
+    let mut stats = Stats::new([
+        (Stat::MaxHealth, character.max_health),
+        (Stat::MoveSpeed, character.velocity),
+        (Stat::Damage, 1.),
+        (Stat::PickupRadius, 50.),
+    ]);
 
-    for angle in 1i32..360i32 {
-        let angle_float = angle as f32;
-        if angle_float % angle_interval == 0. {
-            let x_spawn_float = circle_spawn_radius * angle_float.cos();
-            let y_spawn_float = circle_spawn_radius * angle_float.sin();
+    // Permanent bonuses bought in the shop between runs; a replay brings along the ones it was recorded with.
+    let upgrade_ranks = match &playback {
+        Some(playback) => &playback.replay.upgrade_ranks,
+        None => &save.meta.upgrade_ranks,
+    };
 
-            spawn_enemy_at(&mut commands, &asset_server, &mut texture_atlases, Vec3::new(x_spawn_float, y_spawn_float, 0.));
+    for upgrade in ShopUpgrade::ALL {
+        let bonus = game_data.upgrade(upgrade).bonus(upgrade_ranks.get(&upgrade).copied().unwrap_or(0));
+        if bonus > 0. {
+            stats.add_modifier(StatModifier {
+                stat: upgrade.stat(),
+                source: ModifierSource::Shop,
+                value: ModifierValue::Percent(bonus),
+            });
         }
     }
 
-    // spawn_enemy_at(&mut commands, &asset_server, &mut texture_atlases, Vec3::new(-400., 50., 0.));
-
     commands.spawn(
         (
             SpriteSheetBundle {
-                texture_atlas: texture_atlas_handle,
+                texture_atlas: game_assets.character_atlases[&character.id].clone(),
+                sprite: TextureAtlasSprite {
+                    color: character.tint,
+                    ..default()
+                },
                 transform: Transform::from_scale(Vec3::splat(2.)),
                 ..default()
             },
             GameEntity,
             PlayerControlled,
+            CameraTarget,
             Movable {
-                velocity: 200.,
+                velocity: stats.get(Stat::MoveSpeed),
                 direction: Vec2::ZERO,
             },
+            MoveStep::default(),
             Collidable {
                 size: Transform::from_scale(Vec3::new(26., 20., 1.))
             },
@@ -108,11 +255,21 @@ fn game_setup(
             Layered,
             AnimationTimer(Timer::from_seconds(0.1, TimerMode::Repeating)),
             Health {
-                max: 100.,
-                current: 100.
-            }
+                max: stats.get(Stat::MaxHealth),
+                current: stats.get(Stat::MaxHealth)
+            },
+            StatusEffects::default(),
+            BaseColor(character.tint),
+            stats,
+            Experience::default(),
         )
     ).with_children(|parent| {
+        parent.spawn((Weapon::new(character.starting_weapon, game_data.weapon(character.starting_weapon)), GameEntity));
+
+        if let Some(passive) = character.passive {
+            parent.spawn((Passive { kind: passive }, GameEntity));
+        }
+
         parent.spawn(
             (
                 SpriteBundle {
@@ -145,34 +302,18 @@ fn game_setup(
                 )
             );
         });
-
-        parent.spawn(
-            (
-                Camera2dBundle {
-                    transform: Transform::from_translation(Vec3::new(0., 0., 999.)),
-                    projection: OrthographicProjection {
-                        scale: 0.5,
-                        ..default()
-                    },
-                    ..default()
-                },
-                GameEntity,
-            )
-        );
     });
 }
 
-fn spawn_tree_at(
+pub fn spawn_tree_at(
     commands: &mut Commands,
-    asset_server: &Res<AssetServer>,
+    game_assets: &GameAssets,
     vec3_translation: Vec3,
 ) {
-    let tree_texture_handle = asset_server.load("environment/tree-sprite.png");
-
     commands.spawn(
         (
             SpriteBundle {
-                texture: tree_texture_handle,
+                texture: game_assets.tree.clone(),
                 sprite: Sprite {
                     anchor: Anchor::Custom(Vec2::new(0., -0.25)),
                     custom_size: Some(Vec2::new(60., 60.)),
@@ -190,66 +331,14 @@ fn spawn_tree_at(
         )
     );
 }
+
+// Leaving a run never leaves the game paused for the next one.
+fn resume_game(mut game_state: ResMut<NextState<GameState>>) {
+    game_state.set(GameState::Running);
+}
+
 fn despawn_screen<T: Component>(to_despawn: Query<Entity, With<T>>, mut commands: Commands) {
     for entity in &to_despawn {
         commands.entity(entity).despawn_recursive();
     }
 }
-
-// Tag interface for all enemies.
-#[derive(Component)]
-pub struct Enemy;
-
-// Tag interface for marking enemies that will not follow the regular movement pattern of always pathing towards the player.
-#[derive(Component)]
-pub struct IrregularEnemy;
-
-// Makes use of the Movable struct to point the enemy to always walk towards the player character.
-pub fn regular_enemy_movement(
-    player_character: Query<(
-        &Movable, &mut Transform, &Collidable),
-        (With<PlayerControlled>, (Without<Camera2d>, Without<Enemy>)
-    )>,
-    mut enemies: Query<(&mut Movable, &mut Transform, &Collidable), (With<Enemy>, Without<IrregularEnemy>)>,
-) {
-    let (_player_movable, player_transform, _player_collide) = player_character.single();
-
-    for (mut enemy_movable, enemy_transform, _) in &mut enemies {
-        let target_dir = player_transform.translation.truncate() - enemy_transform.translation.truncate();
-        enemy_movable.direction = target_dir;
-    }
-}
-
-pub fn spawn_enemy_at(
-    commands: &mut Commands,
-    asset_server: &Res<AssetServer>,
-    texture_atlases: &mut ResMut<Assets<TextureAtlas>>,
-    vec3_translation: Vec3
-) {
-    let texture_handle = asset_server.load("player_character/gabe-idle-run.png");
-    let texture_atlas = TextureAtlas::from_grid(texture_handle, Vec2::new(24.0, 24.0), 7, 1, None, None);
-    let texture_atlas_handle = texture_atlases.add(texture_atlas);
-
-    commands.spawn(
-        (
-            SpriteSheetBundle {
-                texture_atlas: texture_atlas_handle,
-                transform: Transform::from_scale(Vec3::splat(2.))
-                    .with_translation(vec3_translation),
-                ..default()
-            },
-            Movable {
-                velocity: 150.,
-                direction: Vec2::ZERO,
-            },
-            Collidable {
-                size: Transform::from_scale(Vec3::new(26., 20., 1.))
-            },
-            GameEntity,
-            FacingDirection::East,
-            Layered,
-            Enemy,
-            AnimationTimer(Timer::from_seconds(0.1, TimerMode::Repeating))
-        )
-    );
-}
\ No newline at end of file
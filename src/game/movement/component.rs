@@ -11,7 +11,7 @@ pub struct Collidable {
 // this is required to support uneven shapes.
 impl Collidable {
     pub(crate) fn size(self: &Collidable) -> Vec2 {
-        return self.size.scale.truncate();
+        self.size.scale.truncate()
     }
 }
 
@@ -21,6 +21,11 @@ pub struct Movable {
     pub direction: Vec2
 }
 
+// How far the entity moves this tick once collisions are resolved; written by `resolve_movement_system`
+// and applied by `apply_movement_system`.
+#[derive(Component, Default)]
+pub struct MoveStep(pub Vec2);
+
 impl Movable {
     pub fn get_x_direction(self: &Movable) -> f32 {
         Vec2::as_ref(&Vec2::normalize_or_zero(self.direction))[0]
@@ -29,4 +34,34 @@ impl Movable {
     pub fn get_y_direction(self: &Movable) -> f32 {
         Vec2::as_ref(&Vec2::normalize_or_zero(self.direction))[1]
     }
-}
\ No newline at end of file
+}
+
+// How quickly knockback impulses die out, per second.
+const KNOCKBACK_DAMPING: f32 = 10.;
+
+// An impulse pushing the entity around on top of its own movement, e.g. after being hit.
+// While `stun_secs` is running the entity's own AI should leave it be.
+#[derive(Component, Default)]
+pub struct Knockback {
+    pub impulse: Vec2,
+    pub stun_secs: f32,
+}
+
+impl Knockback {
+    pub fn is_stunned(&self) -> bool {
+        self.stun_secs > 0.
+    }
+
+    pub fn tick(&mut self, delta_secs: f32) {
+        self.impulse *= (-KNOCKBACK_DAMPING * delta_secs).exp();
+        if self.impulse.length_squared() < 1. {
+            self.impulse = Vec2::ZERO;
+        }
+
+        self.stun_secs = (self.stun_secs - delta_secs).max(0.);
+    }
+}
+
+// Fraction of knockback and hit-stun ignored; 1.0 is immune.
+#[derive(Component)]
+pub struct KnockbackResistance(pub f32);
@@ -0,0 +1,10 @@
+use bevy::prelude::*;
+
+// Pushes the target directly away from `source`.
+#[derive(Event)]
+pub struct KnockbackEvent {
+    pub target: Entity,
+    pub source: Vec2,
+    pub force: f32,
+    pub stun_secs: f32,
+}
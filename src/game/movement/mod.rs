@@ -1,2 +1,17 @@
 pub mod component;
-pub mod system;
\ No newline at end of file
+pub mod event;
+pub mod resource;
+pub mod system;
+
+use bevy::prelude::*;
+
+use system::{apply_movement_system, gather_static_colliders_system, resolve_movement_system};
+
+// Moves everything `Movable`: static colliders are gathered first, every mover's step is then resolved
+// against them in parallel, and only after that are the steps applied.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MovementSet;
+
+pub fn movement_systems() -> impl IntoSystemConfigs<()> {
+    (gather_static_colliders_system, resolve_movement_system, apply_movement_system).chain().in_set(MovementSet)
+}
@@ -0,0 +1,81 @@
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+
+// Roughly the size of the largest obstacle, so each one only lands in a handful of cells.
+const CELL_SIZE: f32 = 64.;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Aabb {
+    pub center: Vec2,
+    pub size: Vec2,
+}
+
+impl Aabb {
+    fn cells(&self) -> impl Iterator<Item = IVec2> {
+        let min = ((self.center - self.size / 2.) / CELL_SIZE).floor().as_ivec2();
+        let max = ((self.center + self.size / 2.) / CELL_SIZE).floor().as_ivec2();
+
+        (min.y..=max.y).flat_map(move |y| (min.x..=max.x).map(move |x| IVec2::new(x, y)))
+    }
+}
+
+// Everything movement is resolved against: the level's bounds and every collider that doesn't move itself,
+// bucketed into a grid so a mover only has to look at the obstacles around it. Only rebuilt when those change.
+#[derive(Resource, Default)]
+pub struct StaticColliders {
+    pub level: Option<Aabb>,
+    colliders: Vec<Aabb>,
+    cells: HashMap<IVec2, Vec<u32>>,
+}
+
+impl StaticColliders {
+    pub fn rebuild(&mut self, level: Option<Aabb>, colliders: impl IntoIterator<Item = Aabb>) {
+        self.level = level;
+        self.colliders.clear();
+        self.colliders.extend(colliders);
+        self.cells.clear();
+
+        for (index, collider) in self.colliders.iter().enumerate() {
+            for cell in collider.cells() {
+                self.cells.entry(cell).or_default().push(index as u32);
+            }
+        }
+    }
+
+    pub fn colliders(&self) -> &[Aabb] {
+        &self.colliders
+    }
+
+    // Every collider sharing a grid cell with `area`, which includes all that overlap it. Colliders spanning
+    // several cells can come up more than once.
+    pub fn near(&self, area: Aabb) -> impl Iterator<Item = &Aabb> {
+        area.cells()
+            .filter_map(|cell| self.cells.get(&cell))
+            .flatten()
+            .map(|index| &self.colliders[*index as usize])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_every_collider_overlapping_an_area() {
+        let trees: Vec<_> = (-10..=10)
+            .map(|i| Aabb { center: Vec2::new(i as f32 * 50., (i as f32).cos() * 100.), size: Vec2::new(16., 20.) })
+            .collect();
+        let mut static_colliders = StaticColliders::default();
+        static_colliders.rebuild(None, trees.iter().copied());
+
+        for x in (-600..600).step_by(7) {
+            let area = Aabb { center: Vec2::new(x as f32, 30.), size: Vec2::new(26., 150.) };
+            let near: Vec<_> = static_colliders.near(area).collect();
+
+            for tree in &trees {
+                let overlaps = (tree.center - area.center).abs().cmplt((tree.size + area.size) / 2.).all();
+                assert!(!overlaps || near.contains(&tree), "missed {:?} around {:?}", tree, area);
+            }
+        }
+    }
+}
@@ -1,65 +1,192 @@
 use bevy::prelude::*;
 use bevy::sprite::collide_aabb::{collide, Collision};
 use crate::game::level::component::LevelFloor;
-use crate::game::movement::component::{ Collidable, Movable };
+use crate::game::movement::component::{ Collidable, Knockback, KnockbackResistance, Movable, MoveStep };
+use crate::game::movement::event::KnockbackEvent;
+use crate::game::movement::resource::{Aabb, StaticColliders};
 
-pub fn entity_movement_system(
-    time: Res<Time>,
-    mut movable_entities: Query<(&Movable, &mut Transform, &Collidable)>,
-    static_entities: Query<(&Transform, &Collidable), (Without<Movable>, Without<LevelFloor>)>,
-    level: Query<(&Transform, &Sprite), (With<LevelFloor>, Without<Collidable>)>
+// Keeps `StaticColliders` in line with the level and the obstacles in it. Anything that moves is left out, so
+// enemies coming and going don't count as the level changing.
+#[allow(clippy::type_complexity)]
+pub fn gather_static_colliders_system(
+    mut static_colliders: ResMut<StaticColliders>,
+    obstacles: Query<(&Transform, &Collidable), (Without<Movable>, Without<LevelFloor>)>,
+    levels: Query<(&Transform, &Sprite), (With<LevelFloor>, Without<Collidable>)>,
+    changed_obstacles: Query<(), (With<Collidable>, Without<Movable>, Or<(Changed<Transform>, Changed<Collidable>)>)>,
+    changed_levels: Query<(), (With<LevelFloor>, Or<(Changed<Transform>, Changed<Sprite>)>)>,
 ) {
-    let (level_transform, level_sprite) = level.single();
-
-    for(movable, mut transform, collidable) in &mut movable_entities {
-        let translation = transform.translation;
-
-        // info!("movable entity position at: <{},{},{}>", translation.x, translation.y, translation.z);
-        // info!("movable entity movable direction: <{},{}>", movable.get_x_direction(), movable.get_y_direction());
-        let mut y_move = movable.get_y_direction() * movable.velocity * time.delta_seconds();
-        let mut x_move = movable.get_x_direction() * movable.velocity * time.delta_seconds();
-
-        let mut next_translation = translation.clone();
-        next_translation.x += x_move;
-        next_translation.y += y_move;
-
-        for (static_entity_transform, static_entity_collidable) in &static_entities {
-            let mut static_entity_translation = static_entity_transform.translation.clone();
-
-            // bevy's collision works in 3d, let's make sure the translations are in the same z axis.
-            next_translation.z = 1.0;
-            static_entity_translation.z = 1.0;
-
-            match collide(next_translation, collidable.size(), static_entity_translation, static_entity_collidable.size()) {
-                None => {},
-                Some(collision) => {
-                    match collision {
-                        Collision::Top => { if y_move.lt(&0.) { y_move = 0. } },
-                        Collision::Bottom => { if y_move.gt(&0.) { y_move = 0. } },
-                        Collision::Left => { if x_move.gt(&0.) { x_move = 0. } },
-                        Collision::Right => { if x_move.lt(&0.) { x_move = 0. } },
-                        Collision::Inside => {
-                            x_move = 0.;
-                            y_move= 0.;
-                        }
-                    }
-                }
-            }
+    // Removals are spotted by counting rather than through `RemovedComponents`, which is cleared every frame and
+    // so misses whatever happened in frames without a tick. Anything added shows up as changed.
+    let removed = obstacles.iter().count() != static_colliders.colliders().len()
+        || levels.is_empty() == static_colliders.level.is_some();
+    if changed_obstacles.is_empty() && changed_levels.is_empty() && !removed {
+        return;
+    }
+
+    let level = levels.get_single().ok().map(|(transform, sprite)| Aabb {
+        center: transform.translation.truncate(),
+        size: sprite.custom_size.expect("No levelsprite custom size; assuming no level loaded."),
+    });
+
+    static_colliders.rebuild(level, obstacles.iter().map(|(transform, collidable)| Aabb {
+        center: transform.translation.truncate(),
+        size: collidable.size(),
+    }));
+}
+
+// Works out every mover's step for this tick in parallel. Each one only writes to itself and reads
+// the shared `StaticColliders`, so no mover has to wait on any other.
+pub fn resolve_movement_system(
+    fixed_time: Res<FixedTime>,
+    static_colliders: Res<StaticColliders>,
+    mut movable_entities: Query<(&Movable, &Transform, &Collidable, Option<&mut Knockback>, &mut MoveStep)>,
+) {
+    let _span = info_span!("movement").entered();
+
+    let Some(level) = static_colliders.level else { return };
+    let delta_secs = fixed_time.period.as_secs_f32();
+
+    movable_entities.par_iter_mut().for_each_mut(|(movable, transform, collidable, knockback, mut step)| {
+        let mut movement = movable.direction.normalize_or_zero() * movable.velocity * delta_secs;
+
+        // Knockback is resolved against obstacles and the level bounds just like regular movement.
+        if let Some(mut knockback) = knockback {
+            movement += knockback.impulse * delta_secs;
+            knockback.tick(delta_secs);
+        }
+
+        step.0 = resolve_step(transform.translation.truncate(), collidable.size(), movement, &static_colliders, level);
+    });
+}
+
+// Stops movement along each axis that would run into an obstacle, and drops it altogether if it would leave the level.
+fn resolve_step(position: Vec2, size: Vec2, mut movement: Vec2, static_colliders: &StaticColliders, level: Aabb) -> Vec2 {
+    let next = (position + movement).extend(0.);
+
+    for obstacle in static_colliders.near(Aabb { center: next.truncate(), size }) {
+        match collide(next, size, obstacle.center.extend(0.), obstacle.size) {
+            Some(Collision::Top) if movement.y < 0. => movement.y = 0.,
+            Some(Collision::Bottom) if movement.y > 0. => movement.y = 0.,
+            Some(Collision::Left) if movement.x > 0. => movement.x = 0.,
+            Some(Collision::Right) if movement.x < 0. => movement.x = 0.,
+            Some(Collision::Inside) => movement = Vec2::ZERO,
+            _ => {},
+        }
+    }
+
+    match collide(next, size, level.center.extend(0.), level.size) {
+        Some(Collision::Inside) => movement,
+        // Anything but staying fully inside the level means the move is dropped.
+        _ => Vec2::ZERO,
+    }
+}
+
+pub fn apply_movement_system(mut movable_entities: Query<(&mut Transform, &MoveStep)>) {
+    movable_entities.par_iter_mut().for_each_mut(|(mut transform, step)| {
+        if step.0 != Vec2::ZERO {
+            transform.translation += step.0.extend(0.);
         }
+    });
+}
 
-        match collide(next_translation, collidable.size(), level_transform.translation, level_sprite.custom_size.expect("No levelsprite custom size; assuming no level loaded.")) {
-            None => {
-                info!("No collision with level.");
+pub fn apply_knockback_system(
+    mut knockback_events: EventReader<KnockbackEvent>,
+    mut knockable: Query<(&Transform, &mut Knockback, Option<&KnockbackResistance>)>,
+) {
+    for event in knockback_events.iter() {
+        let Ok((transform, mut knockback, resistance)) = knockable.get_mut(event.target) else { continue };
+        let susceptibility = 1. - resistance.map_or(0., |resistance| resistance.0.clamp(0., 1.));
+        let direction = (transform.translation.truncate() - event.source).normalize_or_zero();
+
+        knockback.impulse += direction * event.force * susceptibility;
+        knockback.stun_secs = knockback.stun_secs.max(event.stun_secs * susceptibility);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::movement::{movement_systems, MovementSet};
+
+    fn world_with_level() -> World {
+        let mut world = World::new();
+        world.insert_resource(FixedTime::new_from_secs(0.1));
+        world.init_resource::<Events<KnockbackEvent>>();
+        world.init_resource::<StaticColliders>();
+        world.spawn((
+            Transform::default(),
+            Sprite {
+                custom_size: Some(Vec2::new(200., 200.)),
+                ..default()
             },
-            Some(collision) => {
-                match collision {
-                    Collision::Inside => {
-                        transform.translation.x += x_move;
-                        transform.translation.y += y_move;
-                    },
-                    _ => {}
-                }
-            }
+            LevelFloor,
+        ));
+
+        world
+    }
+
+    fn spawn_knockable(world: &mut World, x: f32, resistance: f32) -> Entity {
+        world.spawn((
+            Transform::from_xyz(x, 0., 0.),
+            Movable { velocity: 0., direction: Vec2::ZERO },
+            MoveStep::default(),
+            Collidable { size: Transform::from_scale(Vec3::new(10., 10., 1.)) },
+            Knockback::default(),
+            KnockbackResistance(resistance),
+        )).id()
+    }
+
+    fn run(world: &mut World) {
+        let mut schedule = Schedule::default();
+        schedule.add_systems((apply_knockback_system.before(MovementSet), movement_systems()));
+        schedule.run(world);
+    }
+
+    #[test]
+    fn knockback_pushes_away_from_source_scaled_by_resistance() {
+        let mut world = world_with_level();
+        let light = spawn_knockable(&mut world, 0., 0.);
+        let heavy = spawn_knockable(&mut world, 0., 0.75);
+
+        for target in [light, heavy] {
+            world.send_event(KnockbackEvent { target, source: Vec2::new(-10., 0.), force: 100., stun_secs: 1. });
         }
+        run(&mut world);
+
+        assert!((world.get::<Transform>(light).unwrap().translation.x - 10.).abs() < 0.001);
+        assert!((world.get::<Transform>(heavy).unwrap().translation.x - 2.5).abs() < 0.001);
+        assert!((world.get::<Knockback>(heavy).unwrap().stun_secs - 0.15).abs() < 0.001);
+    }
+
+    #[test]
+    fn knockback_respects_obstacles_and_level_bounds() {
+        let mut world = world_with_level();
+        // Right next to the level edge and a tree, both of which should stop the push.
+        let near_edge = spawn_knockable(&mut world, 94., 0.);
+        let near_tree = spawn_knockable(&mut world, -50., 0.);
+        world.spawn((Transform::from_xyz(-39., 0., 0.), Collidable { size: Transform::from_scale(Vec3::new(10., 10., 1.)) }));
+
+        world.send_event(KnockbackEvent { target: near_edge, source: Vec2::new(0., 0.), force: 100., stun_secs: 0. });
+        world.send_event(KnockbackEvent { target: near_tree, source: Vec2::new(-100., 0.), force: 100., stun_secs: 0. });
+        run(&mut world);
+
+        assert_eq!(world.get::<Transform>(near_edge).unwrap().translation.x, 94.);
+        assert_eq!(world.get::<Transform>(near_tree).unwrap().translation.x, -50.);
+    }
+
+    #[test]
+    fn static_colliders_notice_removals_from_frames_without_a_tick() {
+        let mut world = world_with_level();
+        let tree = world.spawn((Transform::from_xyz(-39., 0., 0.), Collidable { size: Transform::from_scale(Vec3::new(10., 10., 1.)) })).id();
+        spawn_knockable(&mut world, 50., 0.);
+        run(&mut world);
+        assert_eq!(world.resource::<StaticColliders>().colliders().len(), 1);
+
+        world.despawn(tree);
+        // What happens at the end of every frame, whether or not a tick ran in it.
+        world.clear_trackers();
+        run(&mut world);
+
+        assert!(world.resource::<StaticColliders>().colliders().is_empty());
     }
-}
\ No newline at end of file
+}
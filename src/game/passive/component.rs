@@ -0,0 +1,25 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum PassiveKind {
+    // Restores a small amount of health every second.
+    Regeneration,
+    // Increases all damage dealt.
+    Might,
+}
+
+impl PassiveKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            PassiveKind::Regeneration => "Regeneration",
+            PassiveKind::Might => "Might",
+        }
+    }
+}
+
+// Like weapons, passives live on child entities of the entity they affect.
+#[derive(Component)]
+pub struct Passive {
+    pub kind: PassiveKind,
+}
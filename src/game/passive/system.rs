@@ -0,0 +1,46 @@
+use bevy::prelude::*;
+
+use crate::game::health::component::Health;
+use crate::game::passive::component::{Passive, PassiveKind};
+use crate::game::stats::component::{ModifierSource, ModifierValue, Stat, StatModifier, Stats};
+
+const REGENERATION_PER_SECOND: f32 = 0.5;
+const MIGHT_DAMAGE_BONUS: f32 = 0.2;
+
+pub fn regeneration_system(
+    fixed_time: Res<FixedTime>,
+    passives: Query<(&Passive, &Parent)>,
+    mut owners: Query<&mut Health>,
+) {
+    for (passive, parent) in &passives {
+        if passive.kind != PassiveKind::Regeneration {
+            continue;
+        }
+
+        if let Ok(mut health) = owners.get_mut(parent.get()) {
+            if health.current > 0. {
+                health.current = (health.current + REGENERATION_PER_SECOND * fixed_time.period.as_secs_f32()).min(health.max);
+            }
+        }
+    }
+}
+
+// Might is a permanent stat modifier, so it only has to be added once when the passive is gained.
+pub fn might_system(
+    passives: Query<(&Passive, &Parent), Added<Passive>>,
+    mut owners: Query<&mut Stats>,
+) {
+    for (passive, parent) in &passives {
+        if passive.kind != PassiveKind::Might {
+            continue;
+        }
+
+        if let Ok(mut stats) = owners.get_mut(parent.get()) {
+            stats.add_modifier(StatModifier {
+                stat: Stat::Damage,
+                source: ModifierSource::Passive(PassiveKind::Might),
+                value: ModifierValue::Percent(MIGHT_DAMAGE_BONUS),
+            });
+        }
+    }
+}
@@ -0,0 +1,270 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use bevy::prelude::*;
+
+use crate::game::movement::resource::Aabb;
+
+pub const CELL_SIZE: f32 = 32.;
+// Obstacles are grown by about half an enemy so paths leave room to squeeze past them.
+const CLEARANCE: f32 = 12.;
+const STRAIGHT_COST: u32 = 10;
+const DIAGONAL_COST: u32 = 14;
+const UNREACHABLE: u32 = u32::MAX;
+
+const NEIGHBOURS: [(IVec2, u32); 8] = [
+    (IVec2::new(1, 0), STRAIGHT_COST),
+    (IVec2::new(-1, 0), STRAIGHT_COST),
+    (IVec2::new(0, 1), STRAIGHT_COST),
+    (IVec2::new(0, -1), STRAIGHT_COST),
+    (IVec2::new(1, 1), DIAGONAL_COST),
+    (IVec2::new(-1, 1), DIAGONAL_COST),
+    (IVec2::new(1, -1), DIAGONAL_COST),
+    (IVec2::new(-1, -1), DIAGONAL_COST),
+];
+
+// A grid over the level holding every cell's walking distance to the player, from which each enemy can look up
+// which way leads around the obstacles in front of it. Distances are recomputed from scratch when the player moves
+// into another cell, since every one of them depends on where the player is. Obstacles are only rasterised when they
+// change; cells an obstacle frees up, as when a prop breaks, are repaired by searching on from the cells around them,
+// and only a cell becoming blocked, which can make paths longer, recomputes everything.
+#[derive(Resource, Default)]
+pub struct FlowField {
+    origin: Vec2,
+    size: IVec2,
+    blocked: Vec<bool>,
+    costs: Vec<u32>,
+    goal: Option<IVec2>,
+}
+
+impl FlowField {
+    pub fn rebuild_obstacles<'a>(&mut self, level: Aabb, obstacles: impl IntoIterator<Item = &'a Aabb>) {
+        let origin = level.center - level.size / 2.;
+        let size = (level.size / CELL_SIZE).ceil().as_ivec2().max(IVec2::ONE);
+        let same_level = origin == self.origin && size == self.size && !self.blocked.is_empty();
+
+        self.origin = origin;
+        self.size = size;
+        let previously_blocked = std::mem::replace(&mut self.blocked, vec![false; (size.x * size.y) as usize]);
+
+        for obstacle in obstacles {
+            let min = self.cell(obstacle.center - obstacle.size / 2. - CLEARANCE);
+            let max = self.cell(obstacle.center + obstacle.size / 2. + CLEARANCE);
+
+            for y in min.y..=max.y {
+                for x in min.x..=max.x {
+                    let index = self.index(IVec2::new(x, y));
+                    self.blocked[index] = true;
+                }
+            }
+        }
+
+        let Some(goal) = self.goal.filter(|_| same_level) else {
+            self.costs = vec![UNREACHABLE; self.blocked.len()];
+            self.goal = None;
+            return;
+        };
+
+        let changed = previously_blocked.iter().zip(&self.blocked).enumerate().filter(|(_, (before, now))| before != now);
+        let mut freed = Vec::new();
+        for (index, (_, &now)) in changed {
+            if now {
+                // Paths through the cell are gone, and anything further along them has to be worked out again.
+                self.search_from(goal);
+                return;
+            }
+            freed.push(index);
+        }
+
+        self.repair(&freed);
+    }
+
+    // Points the field at `target`; a no-op while it stays in the same cell.
+    pub fn update(&mut self, target: Vec2) {
+        if self.blocked.is_empty() {
+            return;
+        }
+
+        let goal = self.cell(target);
+        if self.goal == Some(goal) {
+            return;
+        }
+
+        self.search_from(goal);
+    }
+
+    // Dijkstra outwards from the goal. The goal cell is walkable even when the target stands right next to a tree.
+    fn search_from(&mut self, goal: IVec2) {
+        self.goal = Some(goal);
+        self.costs.fill(UNREACHABLE);
+
+        let goal_index = self.index(goal);
+        self.costs[goal_index] = 0;
+        self.expand(BinaryHeap::from([Reverse((0, goal_index))]));
+    }
+
+    // Freed cells can only make paths shorter, so the search carries on from every cell around them with the
+    // distances it already has. That also covers diagonal steps a freed corner used to cut off.
+    fn repair(&mut self, freed: &[usize]) {
+        let mut open = BinaryHeap::new();
+
+        for &index in freed {
+            let cell = self.cell_at(index);
+            for (offset, _) in NEIGHBOURS {
+                let next = cell + offset;
+                if !self.in_bounds(next) {
+                    continue;
+                }
+
+                let next_index = self.index(next);
+                if self.costs[next_index] != UNREACHABLE {
+                    open.push(Reverse((self.costs[next_index], next_index)));
+                }
+            }
+        }
+
+        self.expand(open);
+    }
+
+    fn expand(&mut self, mut open: BinaryHeap<Reverse<(u32, usize)>>) {
+        while let Some(Reverse((cost, index))) = open.pop() {
+            if cost > self.costs[index] {
+                continue;
+            }
+
+            let cell = self.cell_at(index);
+            for (offset, step_cost) in NEIGHBOURS {
+                let next = cell + offset;
+                if !self.can_step(cell, next) {
+                    continue;
+                }
+
+                let next_index = self.index(next);
+                let next_cost = cost + step_cost;
+                if next_cost < self.costs[next_index] {
+                    self.costs[next_index] = next_cost;
+                    open.push(Reverse((next_cost, next_index)));
+                }
+            }
+        }
+    }
+
+    // The way to walk from `position` towards the target, or `None` where heading straight for it is best:
+    // in the target's own cell, outside the level, or before the field has been worked out.
+    pub fn direction(&self, position: Vec2) -> Option<Vec2> {
+        let cell = self.grid_cell(position)?;
+        let mut best = (self.costs[self.index(cell)], None);
+
+        for (offset, _) in NEIGHBOURS {
+            let next = cell + offset;
+            // Entities already stuck in an obstacle's clearance may still walk out of it.
+            if !self.in_bounds(next) || self.is_blocked(next) || (offset.x != 0 && offset.y != 0 && !self.can_step(cell, next)) {
+                continue;
+            }
+
+            let cost = self.costs[self.index(next)];
+            if cost < best.0 {
+                best = (cost, Some(next));
+            }
+        }
+
+        best.1.map(|next| (self.center(next) - position).normalize_or_zero())
+    }
+
+    fn can_step(&self, from: IVec2, to: IVec2) -> bool {
+        // Diagonal steps don't cut corners, which would run enemies into the obstacle's edge.
+        self.in_bounds(to)
+            && !self.is_blocked(to)
+            && !self.is_blocked(IVec2::new(to.x, from.y))
+            && !self.is_blocked(IVec2::new(from.x, to.y))
+    }
+
+    fn is_blocked(&self, cell: IVec2) -> bool {
+        self.blocked[self.index(cell)]
+    }
+
+    fn in_bounds(&self, cell: IVec2) -> bool {
+        cell.cmpge(IVec2::ZERO).all() && cell.cmplt(self.size).all()
+    }
+
+    fn grid_cell(&self, position: Vec2) -> Option<IVec2> {
+        let cell = ((position - self.origin) / CELL_SIZE).floor().as_ivec2();
+        (!self.blocked.is_empty() && self.in_bounds(cell)).then_some(cell)
+    }
+
+    // Like `grid_cell`, but positions outside the level count as being in the nearest cell.
+    fn cell(&self, position: Vec2) -> IVec2 {
+        ((position - self.origin) / CELL_SIZE).floor().as_ivec2().clamp(IVec2::ZERO, self.size - 1)
+    }
+
+    fn center(&self, cell: IVec2) -> Vec2 {
+        self.origin + (cell.as_vec2() + 0.5) * CELL_SIZE
+    }
+
+    fn index(&self, cell: IVec2) -> usize {
+        (cell.y * self.size.x + cell.x) as usize
+    }
+
+    fn cell_at(&self, index: usize) -> IVec2 {
+        IVec2::new(index as i32 % self.size.x, index as i32 / self.size.x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn leads_around_a_wall_to_the_target() {
+        let level = Aabb { center: Vec2::ZERO, size: Vec2::new(1000., 1000.) };
+        // A wall between the walker and the target, open only at its top end.
+        let wall: Vec<_> = (-20..8)
+            .map(|i| Aabb { center: Vec2::new(0., i as f32 * 20.), size: Vec2::new(16., 20.) })
+            .collect();
+        let mut flow_field = FlowField::default();
+        flow_field.rebuild_obstacles(level, &wall);
+        flow_field.update(Vec2::new(200., 0.));
+
+        let mut position = Vec2::new(-200., 0.);
+        for _ in 0..200 {
+            let Some(direction) = flow_field.direction(position) else { break };
+            position += direction * 8.;
+
+            let touching_wall = wall.iter().any(|tree| (tree.center - position).abs().cmplt(tree.size / 2. + 5.).all());
+            assert!(!touching_wall, "walked into the wall at {:?}", position);
+        }
+
+        assert_eq!(flow_field.cell(position), flow_field.cell(Vec2::new(200., 0.)));
+    }
+
+    #[test]
+    fn repairs_match_working_the_field_out_from_scratch() {
+        let level = Aabb { center: Vec2::ZERO, size: Vec2::new(1000., 1000.) };
+        // Two crossing rows of crates.
+        let crates: Vec<_> = (-12..12)
+            .flat_map(|i| [Vec2::new(i as f32 * 30., 50.), Vec2::new(-100., i as f32 * 30.)])
+            .map(|center| Aabb { center, size: Vec2::splat(20.) })
+            .collect();
+        let target = Vec2::new(40., 300.);
+
+        let fresh = |obstacles: &[Aabb]| {
+            let mut flow_field = FlowField::default();
+            flow_field.rebuild_obstacles(level, obstacles);
+            flow_field.update(target);
+            flow_field.costs
+        };
+
+        let mut flow_field = FlowField::default();
+        flow_field.rebuild_obstacles(level, &crates);
+        flow_field.update(target);
+
+        // Breaking crates one at a time frees cells; putting them all back blocks them again.
+        for broken in 1..crates.len() {
+            flow_field.rebuild_obstacles(level, &crates[broken..]);
+            assert!(flow_field.costs == fresh(&crates[broken..]), "repair went wrong after breaking {} crates", broken);
+        }
+
+        flow_field.rebuild_obstacles(level, &crates);
+        assert!(flow_field.costs == fresh(&crates));
+    }
+}
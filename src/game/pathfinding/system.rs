@@ -0,0 +1,23 @@
+use bevy::prelude::*;
+
+use crate::game::movement::resource::StaticColliders;
+use crate::game::pathfinding::resource::FlowField;
+use crate::game::player::component::PlayerControlled;
+
+// Runs after movement, so enemies steer by where the player and the obstacles ended up last tick.
+pub fn flow_field_system(
+    static_colliders: Res<StaticColliders>,
+    player: Query<&Transform, With<PlayerControlled>>,
+    mut flow_field: ResMut<FlowField>,
+) {
+    let _span = info_span!("flow field").entered();
+
+    if static_colliders.is_changed() {
+        if let Some(level) = static_colliders.level {
+            flow_field.rebuild_obstacles(level, static_colliders.colliders());
+        }
+    }
+
+    let Ok(player_transform) = player.get_single() else { return };
+    flow_field.update(player_transform.translation.truncate());
+}
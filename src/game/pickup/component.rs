@@ -0,0 +1,53 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::game::pool::resource::PoolKind;
+use crate::game::weapon::component::WeaponKind;
+
+// Something lying on the floor that the player collects by walking within their pickup radius stat.
+#[derive(Component, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum Pickup {
+    Gold(u32),
+    // Restores this much health, up to the maximum.
+    Heal(f32),
+    // Pulls every pickup on the floor towards the player.
+    Magnet,
+    // Deals this much damage to every enemy on screen.
+    Bomb(f32),
+    // Slows every enemy down for this many seconds.
+    Freeze(f32),
+    // Ranks up one of the player's weapons, or grants a random upgrade for the rest of the run.
+    Chest,
+    // Offers to evolve a weapon that's ready for it; opens like a regular chest if none is or the offer is turned down.
+    BossChest,
+}
+
+impl Pickup {
+    pub fn validate(&self) -> Result<(), String> {
+        match *self {
+            Pickup::Gold(0) => Err("gold pickups need a positive amount".to_string()),
+            Pickup::Heal(amount) | Pickup::Bomb(amount) | Pickup::Freeze(amount) if amount <= 0. => Err(format!("{:?} needs a positive amount", self)),
+            _ => Ok(()),
+        }
+    }
+}
+
+// A boss chest's offer to turn one of the player's weapons into `into`, waiting on the player to take it or not.
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct EvolutionOffer {
+    pub weapon: Entity,
+    pub into: WeaponKind,
+}
+
+// The prompt shown while the player has an `EvolutionOffer` to answer.
+#[derive(Component)]
+pub struct EvolutionOfferText;
+
+// Flies towards the player after a magnet was picked up.
+#[derive(Component)]
+pub struct Magnetised;
+
+impl PoolKind for Pickup {
+    const NAME: &'static str = "pickup";
+    type Active = (Pickup, Magnetised);
+}
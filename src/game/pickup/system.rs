@@ -0,0 +1,282 @@
+use bevy::prelude::*;
+use bevy::ecs::query::Has;
+use rand::seq::SliceRandom;
+
+use crate::audio::event::{Sfx, SfxEvent};
+use crate::data::resource::GameData;
+use crate::game::GameEntity;
+use crate::game::camera::resource::ViewExtents;
+use crate::game::enemy::component::Enemy;
+use crate::game::health::component::Health;
+use crate::game::health::event::DamageEvent;
+use crate::game::pickup::component::{EvolutionOffer, EvolutionOfferText, Magnetised, Pickup};
+use crate::game::passive::component::Passive;
+use crate::game::player::component::{Experience, PlayerControlled};
+use crate::game::player::resource::{PlayerAction, PlayerInput};
+use crate::game::pool::resource::EntityPool;
+use crate::game::simulation::resource::SimulationRng;
+use crate::game::stats::component::{ModifierSource, ModifierValue, Stat, StatModifier, Stats};
+use crate::game::status::component::{StatusEffect, StatusEffectKind, StatusEffects};
+use crate::game::weapon::component::Weapon;
+use crate::game::weapon::evolution::available_evolution;
+use crate::loading::resource::GameAssets;
+use crate::save::resource::CurrentRun;
+use crate::shop::upgrade::ShopUpgrade;
+
+const MAGNET_SPEED: f32 = 500.;
+const FREEZE_SLOW: f32 = 0.8;
+
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn pickup_collection_system(
+    mut commands: Commands,
+    game_data: Res<GameData>,
+    mut player: Query<(Entity, &Transform, &mut Stats, &mut Health, Has<EvolutionOffer>), With<PlayerControlled>>,
+    mut weapons: Query<(Entity, &mut Weapon, &Parent)>,
+    passives: Query<(&Passive, &Parent)>,
+    pickups: Query<(Entity, &Transform, &Pickup), Without<PlayerControlled>>,
+    mut enemies: Query<(Entity, &Transform, &mut StatusEffects), (With<Enemy>, Without<PlayerControlled>)>,
+    view_extents: Res<ViewExtents>,
+    mut current_run: ResMut<CurrentRun>,
+    mut pickup_pool: ResMut<EntityPool<Pickup>>,
+    mut damage_events: EventWriter<DamageEvent>,
+    mut sfx_events: EventWriter<SfxEvent>,
+    mut rng: ResMut<SimulationRng>,
+) {
+    let Ok((player_entity, player_transform, mut stats, mut health, mut offering)) = player.get_single_mut() else { return };
+    let player_position = player_transform.translation.truncate();
+    let pickup_radius = stats.get(Stat::PickupRadius);
+
+    for (entity, transform, pickup) in &pickups {
+        if transform.translation.truncate().distance(player_position) > pickup_radius {
+            continue;
+        }
+
+        match *pickup {
+            Pickup::Gold(amount) => current_run.gold_earned += amount,
+            Pickup::Heal(amount) => health.current = (health.current + amount).min(health.max),
+            Pickup::Magnet => {
+                // Anything already in reach is collected this tick anyway, and may be back in the pool by now.
+                for (other, other_transform, _) in &pickups {
+                    if other_transform.translation.truncate().distance(player_position) > pickup_radius {
+                        commands.entity(other).insert(Magnetised);
+                    }
+                }
+            }
+            Pickup::Bomb(damage) => {
+                // The camera keeps the player close to the middle of the screen, so this is about what's on it.
+                for (enemy, enemy_transform, _) in &enemies {
+                    let offset = enemy_transform.translation.truncate() - player_position;
+                    if offset.abs().cmple(view_extents.0).all() {
+                        damage_events.send(DamageEvent { target: enemy, amount: damage });
+                    }
+                }
+            }
+            Pickup::Freeze(duration_secs) => {
+                // Applied straight away rather than through `ApplyStatusEvent`, as the status systems already ran this tick.
+                for (_, _, mut status_effects) in &mut enemies {
+                    status_effects.apply(StatusEffect::new(StatusEffectKind::Slow, FREEZE_SLOW, duration_secs));
+                }
+            }
+            Pickup::Chest => {
+                open_chest(&game_data, &mut rng, player_entity, &mut stats, &mut weapons);
+                sfx_events.send(SfxEvent(Sfx::LevelUp));
+            }
+            Pickup::BossChest => {
+                // One offer at a time; any other boss chest opens straight away.
+                match evolution_offer(&game_data, player_entity, &weapons, &passives).filter(|_| !offering) {
+                    Some(offer) => {
+                        commands.entity(player_entity).insert(offer);
+                        offering = true;
+                    }
+                    None => open_chest(&game_data, &mut rng, player_entity, &mut stats, &mut weapons),
+                }
+                sfx_events.send(SfxEvent(Sfx::LevelUp));
+            }
+        }
+
+        sfx_events.send(SfxEvent(Sfx::Pickup));
+        pickup_pool.release(&mut commands, entity);
+    }
+}
+
+// Every level the player reaches opens a chest, on top of the ones found lying around.
+pub fn level_up_system(
+    game_data: Res<GameData>,
+    mut player: Query<(Entity, &mut Experience, &mut Stats), With<PlayerControlled>>,
+    mut weapons: Query<(Entity, &mut Weapon, &Parent)>,
+    mut sfx_events: EventWriter<SfxEvent>,
+    mut rng: ResMut<SimulationRng>,
+) {
+    let Ok((player_entity, mut experience, mut stats)) = player.get_single_mut() else { return };
+
+    let levels = experience.level_up();
+    for _ in 0..levels {
+        open_chest(&game_data, &mut rng, player_entity, &mut stats, &mut weapons);
+    }
+
+    if levels > 0 {
+        sfx_events.send(SfxEvent(Sfx::LevelUp));
+    }
+}
+
+// Settles the player's evolution offer once they answer it: taking it evolves the weapon, turning it down opens the
+// boss chest like a regular one instead.
+pub fn evolution_offer_system(
+    mut commands: Commands,
+    game_data: Res<GameData>,
+    player_input: Res<PlayerInput>,
+    mut player: Query<(Entity, &EvolutionOffer, &mut Stats), With<PlayerControlled>>,
+    mut weapons: Query<(Entity, &mut Weapon, &Parent)>,
+    mut sfx_events: EventWriter<SfxEvent>,
+    mut rng: ResMut<SimulationRng>,
+) {
+    let Ok((player_entity, offer, mut stats)) = player.get_single_mut() else { return };
+
+    if player_input.pressed(PlayerAction::AcceptOffer) {
+        if let Ok((_, mut weapon, _)) = weapons.get_mut(offer.weapon) {
+            *weapon = Weapon::new(offer.into, game_data.weapon(offer.into));
+        }
+    } else if player_input.pressed(PlayerAction::DeclineOffer) {
+        open_chest(&game_data, &mut rng, player_entity, &mut stats, &mut weapons);
+    } else {
+        return;
+    }
+
+    commands.entity(player_entity).remove::<EvolutionOffer>();
+    sfx_events.send(SfxEvent(Sfx::LevelUp));
+}
+
+// The first of the player's weapons that's ready to evolve, given the passives they own.
+fn evolution_offer(
+    game_data: &GameData,
+    player: Entity,
+    weapons: &Query<(Entity, &mut Weapon, &Parent)>,
+    passives: &Query<(&Passive, &Parent)>,
+) -> Option<EvolutionOffer> {
+    let owned_passives: Vec<_> = passives.iter()
+        .filter(|(_, parent)| parent.get() == player)
+        .map(|(passive, _)| passive.kind)
+        .collect();
+
+    weapons.iter()
+        .filter(|(_, _, parent)| parent.get() == player)
+        .find_map(|(entity, weapon, _)| {
+            available_evolution(weapon, game_data.weapon(weapon.kind), &owned_passives)
+                .map(|into| EvolutionOffer { weapon: entity, into })
+        })
+}
+
+// Ranks up a random one of the player's weapons that isn't maxed out yet. Once there's nothing left to rank up,
+// chests grant a stat upgrade for the rest of the run instead.
+fn open_chest(
+    game_data: &GameData,
+    rng: &mut SimulationRng,
+    player: Entity,
+    stats: &mut Stats,
+    weapons: &mut Query<(Entity, &mut Weapon, &Parent)>,
+) {
+    let unranked: Vec<_> = weapons.iter()
+        .filter(|(_, weapon, parent)| parent.get() == player && weapon.rank < game_data.weapon(weapon.kind).max_rank)
+        .map(|(entity, _, _)| entity)
+        .collect();
+
+    if let Some(entity) = unranked.choose(&mut rng.0) {
+        weapons.get_mut(*entity).unwrap().1.rank += 1;
+        return;
+    }
+
+    let upgrade = *ShopUpgrade::ALL.choose(&mut rng.0).unwrap();
+    // Worth one shop rank, on top of whatever was bought; `stats_sync_system` passes it on.
+    stats.add_modifier(StatModifier {
+        stat: upgrade.stat(),
+        source: ModifierSource::Chest,
+        value: ModifierValue::Percent(game_data.upgrade(upgrade).bonus_per_rank),
+    });
+}
+
+pub fn magnetised_pickup_system(
+    fixed_time: Res<FixedTime>,
+    player: Query<&Transform, With<PlayerControlled>>,
+    mut pickups: Query<&mut Transform, (With<Magnetised>, Without<PlayerControlled>)>,
+) {
+    let Ok(player_transform) = player.get_single() else { return };
+    let step = MAGNET_SPEED * fixed_time.period.as_secs_f32();
+
+    for mut transform in &mut pickups {
+        let offset = player_transform.translation.truncate() - transform.translation.truncate();
+        transform.translation += (offset.normalize_or_zero() * step.min(offset.length())).extend(0.);
+    }
+}
+
+pub fn spawn_pickup_at(commands: &mut Commands, pickup_pool: &mut EntityPool<Pickup>, pickup: Pickup, vec3_translation: Vec3) {
+    let (color, size) = match pickup {
+        Pickup::Gold(_) => (Color::rgb(1., 0.84, 0.), 6.),
+        Pickup::Heal(_) => (Color::rgb(0.9, 0.2, 0.3), 8.),
+        Pickup::Magnet => (Color::rgb(0.3, 0.5, 1.), 8.),
+        Pickup::Bomb(_) => (Color::rgb(0.2, 0.2, 0.2), 8.),
+        Pickup::Freeze(_) => (Color::rgb(0.5, 0.9, 1.), 8.),
+        Pickup::Chest => (Color::rgb(0.7, 0.45, 0.1), 12.),
+        Pickup::BossChest => (Color::rgb(0.85, 0.7, 0.2), 16.),
+    };
+
+    pickup_pool.spawn(
+        commands,
+        (
+            SpriteBundle {
+                sprite: Sprite {
+                    color,
+                    custom_size: Some(Vec2::splat(size)),
+                    ..default()
+                },
+                transform: Transform::from_translation(vec3_translation),
+                ..default()
+            },
+            GameEntity,
+            pickup,
+        )
+    );
+}
+
+pub fn setup_evolution_offer_text(mut commands: Commands, game_assets: Res<GameAssets>) {
+    let text = TextBundle {
+        visibility: Visibility::Hidden,
+        ..TextBundle::from_section("", TextStyle {
+            font: game_assets.font.clone(),
+            font_size: 22.,
+            color: Color::WHITE,
+        })
+            .with_style(Style {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(40.),
+                width: Val::Percent(100.),
+                justify_content: JustifyContent::Center,
+                ..default()
+            })
+            .with_text_alignment(TextAlignment::Center)
+            .with_background_color(Color::rgba(0., 0., 0., 0.6))
+    };
+
+    commands.spawn((text, GameEntity, EvolutionOfferText));
+}
+
+// Shows what the pending evolution offer is and how to answer it.
+pub fn evolution_offer_text_system(
+    offers: Query<&EvolutionOffer, With<PlayerControlled>>,
+    weapons: Query<&Weapon>,
+    mut texts: Query<(&mut Text, &mut Visibility), With<EvolutionOfferText>>,
+) {
+    let offer = offers.get_single().ok().and_then(|offer| Some((weapons.get(offer.weapon).ok()?, offer.into)));
+
+    for (mut text, mut visibility) in &mut texts {
+        let Some((weapon, into)) = offer else {
+            *visibility = Visibility::Hidden;
+            continue;
+        };
+
+        *visibility = Visibility::Inherited;
+        text.sections[0].value = format!(
+            "Boss chest: evolve your {} into {}? [E] Evolve  [Q] Open it as a regular chest",
+            weapon.kind.name(), into.name(),
+        );
+    }
+}
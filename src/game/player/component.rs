@@ -1,4 +1,44 @@
 use bevy::prelude::*;
 
 #[derive(Component)]
-pub struct PlayerControlled;
\ No newline at end of file
+pub struct PlayerControlled;
+// Experience gathered this run by killing enemies. Every level reached opens a chest; see `level_up_system`.
+#[derive(Component, Default, Debug, Clone, Copy, PartialEq)]
+pub struct Experience {
+    pub points: u32,
+    pub level: u32,
+}
+
+impl Experience {
+    // Points on top of the previous level's needed to reach `level + 1`, so every level takes a little longer.
+    pub fn to_next_level(level: u32) -> u32 {
+        10 + 5 * level
+    }
+
+    // Moves up as many levels as the points cover and returns how many that was.
+    pub fn level_up(&mut self) -> u32 {
+        let mut gained = 0;
+        while self.points >= Experience::to_next_level(self.level) {
+            self.points -= Experience::to_next_level(self.level);
+            self.level += 1;
+            gained += 1;
+        }
+
+        gained
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn levels_up_as_many_times_as_the_points_allow() {
+        let mut experience = Experience { points: 9, level: 0 };
+        assert_eq!(experience.level_up(), 0);
+
+        experience.points += 21;
+        assert_eq!(experience.level_up(), 2);
+        assert_eq!(experience, Experience { points: 5, level: 2 });
+    }
+}
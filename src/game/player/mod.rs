@@ -1,2 +1,9 @@
 pub mod system;
-pub mod component;
\ No newline at end of file
+pub mod component;
+pub mod resource;
+
+use bevy::prelude::*;
+
+// Fills in the tick's `PlayerInput`, from the keyboard or from a replay; runs in `FixedUpdate` before the player acts.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PlayerInputSet;
@@ -0,0 +1,38 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PlayerAction {
+    MoveUp,
+    MoveDown,
+    MoveLeft,
+    MoveRight,
+    // Answers to a boss chest's `EvolutionOffer`.
+    AcceptOffer,
+    DeclineOffer,
+}
+
+impl PlayerAction {
+    fn bit(self) -> u8 {
+        1 << self as u8
+    }
+}
+
+// The actions held down during the current tick, packed into bits so replays stay small.
+// Player systems read this rather than the keyboard, which lets a replay stand in for the player.
+#[derive(Resource, Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct PlayerInput(pub u8);
+
+impl PlayerInput {
+    pub fn pressed(&self, action: PlayerAction) -> bool {
+        self.0 & action.bit() != 0
+    }
+
+    pub fn set(&mut self, action: PlayerAction, pressed: bool) {
+        if pressed {
+            self.0 |= action.bit();
+        } else {
+            self.0 &= !action.bit();
+        }
+    }
+}
@@ -1,46 +1,52 @@
 use bevy::prelude::*;
 
 use crate::game::movement::component::Movable;
-use crate::game::health::component::Health;
 use crate::game::player::component::PlayerControlled;
+use crate::game::player::resource::{PlayerAction, PlayerInput};
 
-// Handles keyboard events for any PlayerControlled Component-initializes entities.
+// Turns the held keys into this tick's `PlayerInput`.
 // TODO: allow for changing keybinds -- will come with the menu system me thinks
-pub fn keyboard_input_system(
-    mut keyboard_input: ResMut<Input<KeyCode>>,
-    mut player_character: Query<(&mut Movable, &mut Health), With<PlayerControlled>>,
+pub fn keyboard_input_system(keyboard_input: Res<Input<KeyCode>>, mut player_input: ResMut<PlayerInput>) {
+    player_input.set(PlayerAction::MoveUp, keyboard_input.pressed(KeyCode::W));
+    player_input.set(PlayerAction::MoveDown, keyboard_input.pressed(KeyCode::S));
+    player_input.set(PlayerAction::MoveLeft, keyboard_input.pressed(KeyCode::A));
+    player_input.set(PlayerAction::MoveRight, keyboard_input.pressed(KeyCode::D));
+    player_input.set(PlayerAction::AcceptOffer, keyboard_input.pressed(KeyCode::E));
+    player_input.set(PlayerAction::DeclineOffer, keyboard_input.pressed(KeyCode::Q));
+}
+
+// Handles the player's input for any PlayerControlled Component-initializes entities.
+// The direction is rebuilt from the held actions every tick rather than accumulated, so it doesn't depend on how many
+// frames (and key releases) happened between two ticks.
+pub fn player_control_system(
+    player_input: Res<PlayerInput>,
+    mut player_character: Query<&mut Movable, With<PlayerControlled>>,
     // mut app_state: ResMut<NextState<AppState>>,
 ) {
-    for (mut movable, mut health) in &mut player_character {
-        if keyboard_input.pressed(KeyCode::A) {
-            movable.direction += Vec2::new(-0.3, 0.)
-        }
+    for mut movable in &mut player_character {
+        let mut direction = Vec2::ZERO;
 
-        if keyboard_input.pressed(KeyCode::S) {
-            movable.direction += Vec2::new(0., -0.5)
+        if player_input.pressed(PlayerAction::MoveLeft) {
+            direction += Vec2::new(-0.3, 0.)
         }
 
-        if keyboard_input.pressed(KeyCode::W) {
-            movable.direction += Vec2::new(0., 0.5)
+        if player_input.pressed(PlayerAction::MoveDown) {
+            direction += Vec2::new(0., -0.5)
         }
 
-        if keyboard_input.pressed(KeyCode::D) {
-            movable.direction += Vec2::new(0.5, 0.)
+        if player_input.pressed(PlayerAction::MoveUp) {
+            direction += Vec2::new(0., 0.5)
         }
 
-        //TODO: remove after health testing.
-        if keyboard_input.pressed(KeyCode::U) {
-            health.current -= 1.;
-            info!("Removing 1 hp");
+        if player_input.pressed(PlayerAction::MoveRight) {
+            direction += Vec2::new(0.5, 0.)
         }
 
+        movable.direction = direction;
+
         // if keyboard_input.pressed(KeyCode::Space) {
         //     app_state.set(AppState::MainMenu);
         //     keyboard_input.reset(KeyCode::Space);
         // }
-
-        if keyboard_input.any_just_released([KeyCode::W, KeyCode::A, KeyCode::S, KeyCode::D]) {
-            movable.direction = Vec2::ZERO
-        }
     }
-}
\ No newline at end of file
+}
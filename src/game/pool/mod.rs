@@ -0,0 +1,44 @@
+use bevy::prelude::*;
+
+use crate::AppState;
+use crate::game::enemy::component::Enemy;
+use crate::game::feedback::component::DamageNumber;
+use crate::game::pickup::component::Pickup;
+use crate::game::weapon::component::Projectile;
+use resource::{EntityPool, PoolKind};
+
+pub mod resource;
+
+pub struct PoolPlugin;
+
+// Anything spawning pooled entities when a run starts has to run after this.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PoolResetSet;
+
+impl Plugin for PoolPlugin {
+    fn build(&self, app: &mut App) {
+        register_pool::<Enemy>(app);
+        register_pool::<Projectile>(app);
+        register_pool::<Pickup>(app);
+        register_pool::<DamageNumber>(app);
+    }
+}
+
+fn register_pool<K: PoolKind>(app: &mut App) {
+    app.init_resource::<EntityPool<K>>()
+        .add_systems(OnEnter(AppState::GamePlaying), reset_pool::<K>.in_set(PoolResetSet))
+        .add_systems(OnExit(AppState::GamePlaying), log_pool_metrics::<K>);
+}
+
+// Entities from the previous run are gone by the time a new one starts, so start from an empty pool.
+fn reset_pool<K: PoolKind>(mut pool: ResMut<EntityPool<K>>) {
+    pool.reset();
+}
+
+// The F3 overlay shows the same numbers live; this is the summary for the whole run.
+fn log_pool_metrics<K: PoolKind>(pool: Res<EntityPool<K>>) {
+    info!(
+        "{} pool: {} hits, {} misses, {} releases",
+        K::NAME, pool.metrics.hits, pool.metrics.misses, pool.metrics.releases
+    );
+}
@@ -0,0 +1,178 @@
+use std::marker::PhantomData;
+
+use bevy::prelude::*;
+use serde::Serialize;
+
+// Implemented by the main component of each kind of pooled entity.
+pub trait PoolKind: Component {
+    const NAME: &'static str;
+    // Components removed when an entity is released, so gameplay queries stop picking it up while it waits
+    // in the pool. They're inserted again with the rest of the bundle when the entity is reused.
+    type Active: Bundle;
+}
+
+#[derive(Serialize, Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoolMetrics {
+    // Entities handed out from the pool.
+    pub hits: u64,
+    // Entities that had to be spawned because the pool was empty.
+    pub misses: u64,
+    pub releases: u64,
+}
+
+// Recycles short-lived entities instead of spawning and despawning them, which keeps entity ids and
+// archetype tables stable when hundreds of them come and go every second.
+#[derive(Resource)]
+pub struct EntityPool<K: PoolKind> {
+    free: Vec<Entity>,
+    in_use: usize,
+    pub metrics: PoolMetrics,
+    marker: PhantomData<K>,
+}
+
+impl<K: PoolKind> Default for EntityPool<K> {
+    fn default() -> Self {
+        EntityPool {
+            free: Vec::new(),
+            in_use: 0,
+            metrics: PoolMetrics::default(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<K: PoolKind> EntityPool<K> {
+    // Takes a free entity out of the pool if there is one; the caller is responsible for making it visible
+    // and reinserting its `Active` components.
+    pub fn acquire(&mut self) -> Option<Entity> {
+        let entity = self.free.pop();
+        match entity {
+            Some(_) => self.metrics.hits += 1,
+            None => self.metrics.misses += 1,
+        }
+        self.in_use += 1;
+
+        entity
+    }
+
+    // Reuses a free entity for the bundle, or spawns a new one if the pool is empty.
+    // The bundle has to include a `Visibility` (all sprite bundles do) to undo the one set on release.
+    pub fn spawn(&mut self, commands: &mut Commands, bundle: impl Bundle) -> Entity {
+        match self.acquire() {
+            Some(entity) => {
+                commands.entity(entity).insert(bundle);
+                entity
+            },
+            None => commands.spawn(bundle).id(),
+        }
+    }
+
+    // Hides the entity and strips its `Active` components. Must only be called once per use of an entity.
+    pub fn release(&mut self, commands: &mut Commands, entity: Entity) {
+        commands.entity(entity)
+            .remove::<K::Active>()
+            .insert(Visibility::Hidden);
+
+        self.free.push(entity);
+        // Some entities are spawned outside the pool (e.g. pickups placed by the headless tests) and only
+        // join it once released, so there may be nothing handed out to take back.
+        self.in_use = self.in_use.saturating_sub(1);
+        self.metrics.releases += 1;
+    }
+
+    // Adds an entity that's already hidden and inactive, e.g. when warming the pool up.
+    pub fn add_free(&mut self, entity: Entity) {
+        self.free.push(entity);
+    }
+
+    pub fn free_count(&self) -> usize {
+        self.free.len()
+    }
+
+    // Entities handed out by the pool that haven't been released yet.
+    pub fn in_use(&self) -> usize {
+        self.in_use
+    }
+
+    // Forgets all free entities; used when a new run starts and the old ones have been despawned.
+    pub fn reset(&mut self) {
+        self.free.clear();
+        self.in_use = 0;
+        self.metrics = PoolMetrics::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::CommandQueue;
+
+    use super::*;
+
+    #[derive(Component)]
+    struct Bullet;
+
+    #[derive(Component)]
+    struct Speed(f32);
+
+    impl PoolKind for Bullet {
+        const NAME: &'static str = "bullet";
+        type Active = (Bullet, Speed);
+    }
+
+    fn with_commands(world: &mut World, f: impl FnOnce(&mut Commands)) {
+        let mut queue = CommandQueue::default();
+        let mut commands = Commands::new(&mut queue, world);
+        f(&mut commands);
+        queue.apply(world);
+    }
+
+    #[test]
+    fn released_entities_are_reused() {
+        let mut world = World::new();
+        let mut pool = EntityPool::<Bullet>::default();
+
+        let mut first = Entity::PLACEHOLDER;
+        with_commands(&mut world, |commands| first = pool.spawn(commands, (Bullet, Speed(1.), Visibility::Visible)));
+        with_commands(&mut world, |commands| pool.release(commands, first));
+
+        assert!(world.get::<Bullet>(first).is_none());
+        assert!(world.get::<Speed>(first).is_none());
+        assert_eq!(world.get::<Visibility>(first), Some(&Visibility::Hidden));
+
+        let mut second = Entity::PLACEHOLDER;
+        with_commands(&mut world, |commands| second = pool.spawn(commands, (Bullet, Speed(2.), Visibility::Visible)));
+
+        assert_eq!(first, second);
+        assert_eq!(world.get::<Speed>(second).unwrap().0, 2.);
+        assert_eq!(world.get::<Visibility>(second), Some(&Visibility::Visible));
+        assert_eq!(pool.metrics, PoolMetrics { hits: 1, misses: 1, releases: 1 });
+        assert_eq!(pool.free_count(), 0);
+        assert_eq!(pool.in_use(), 1);
+    }
+
+    #[test]
+    fn releasing_entities_spawned_outside_the_pool_keeps_counts_sane() {
+        let mut world = World::new();
+        let mut pool = EntityPool::<Bullet>::default();
+
+        let stray = world.spawn((Bullet, Speed(1.))).id();
+        with_commands(&mut world, |commands| pool.release(commands, stray));
+
+        assert_eq!(pool.in_use(), 0);
+        assert_eq!(pool.free_count(), 1);
+    }
+
+    #[test]
+    fn empty_pool_spawns_new_entities() {
+        let mut world = World::new();
+        let mut pool = EntityPool::<Bullet>::default();
+
+        with_commands(&mut world, |commands| {
+            pool.spawn(commands, Bullet);
+            pool.spawn(commands, Bullet);
+        });
+
+        assert_eq!(world.entities().len(), 2);
+        assert_eq!(pool.metrics.misses, 2);
+    }
+}
@@ -0,0 +1,39 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+
+// Defined in `assets/data/game.props.ron`.
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+pub struct PropArchetype {
+    pub id: String,
+    pub look: PropLook,
+    pub tint: Color,
+    pub size: Vec2,
+    pub collider: Vec2,
+    pub max_health: f32,
+    // How long the break animation plays before the prop is gone.
+    pub break_secs: f32,
+    // The drop table in `assets/data/game.loot.ron` rolled when the prop breaks.
+    pub drops: String,
+}
+
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum PropLook {
+    // The tree sprite, tinted.
+    Tree,
+    // A plain block of the tint colour.
+    Block,
+}
+
+impl PropArchetype {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.size.min_element() <= 0. || self.collider.min_element() <= 0. {
+            return Err(format!("prop {} needs a positive size and collider", self.id));
+        }
+
+        if self.max_health <= 0. || self.break_secs <= 0. {
+            return Err(format!("prop {} needs a positive max health and break time", self.id));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,15 @@
+use bevy::prelude::*;
+
+// Tag interface for destructible scenery.
+#[derive(Component)]
+pub struct Prop;
+
+// The id of the archetype a prop was spawned from.
+#[derive(Component)]
+pub struct PropKind(pub String);
+
+// A prop that's been destroyed, shrinking and fading away until `timer` runs out.
+#[derive(Component)]
+pub struct Breaking {
+    pub timer: Timer,
+}
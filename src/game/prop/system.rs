@@ -0,0 +1,93 @@
+use bevy::prelude::*;
+use bevy::sprite::Anchor;
+use rand::Rng;
+
+use crate::audio::event::{Sfx, SfxEvent};
+use crate::data::resource::GameData;
+use crate::game::GameEntity;
+use crate::game::health::component::Health;
+use crate::game::movement::component::Collidable;
+use crate::game::pickup::component::Pickup;
+use crate::game::pickup::system::spawn_pickup_at;
+use crate::game::pool::resource::EntityPool;
+use crate::game::prop::archetype::{PropArchetype, PropLook};
+use crate::game::prop::component::{Breaking, Prop, PropKind};
+use crate::game::simulation::resource::SimulationRng;
+use crate::loading::resource::GameAssets;
+
+// Props that run out of health stop blocking the way, drop their loot and start breaking apart.
+#[allow(clippy::type_complexity)]
+pub fn prop_break_system(
+    mut commands: Commands,
+    game_data: Res<GameData>,
+    props: Query<(Entity, &Health, &Transform, &PropKind), (With<Prop>, Without<Breaking>)>,
+    mut pickup_pool: ResMut<EntityPool<Pickup>>,
+    mut sfx_events: EventWriter<SfxEvent>,
+    mut rng: ResMut<SimulationRng>,
+) {
+    for (entity, health, transform, kind) in &props {
+        if health.current > 0. {
+            continue;
+        }
+
+        let Some(archetype) = game_data.prop(&kind.0) else { continue };
+        sfx_events.send(SfxEvent(Sfx::Death));
+
+        if let Some(pickup) = game_data.drop_table(&archetype.drops).roll(&mut rng.0) {
+            let scatter = Vec3::new(rng.gen_range(-8. ..8.), rng.gen_range(-8. ..8.), 0.);
+            spawn_pickup_at(&mut commands, &mut pickup_pool, pickup, transform.translation + scatter);
+        }
+
+        commands.entity(entity)
+            .remove::<(Collidable, Health)>()
+            .insert(Breaking { timer: Timer::from_seconds(archetype.break_secs, TimerMode::Once) });
+    }
+}
+
+pub fn prop_breaking_system(
+    mut commands: Commands,
+    fixed_time: Res<FixedTime>,
+    mut props: Query<(Entity, &mut Breaking, &mut Transform, &mut Sprite)>,
+) {
+    for (entity, mut breaking, mut transform, mut sprite) in &mut props {
+        if breaking.timer.tick(fixed_time.period).finished() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+
+        let progress = breaking.timer.percent();
+        transform.scale = Vec3::new(1. + progress * 0.3, 1. - progress * 0.7, 1.);
+        sprite.color.set_a(1. - progress);
+    }
+}
+
+pub fn spawn_prop_at(commands: &mut Commands, game_assets: &GameAssets, archetype: &PropArchetype, vec3_translation: Vec3) {
+    let (texture, anchor) = match archetype.look {
+        PropLook::Tree => (game_assets.tree.clone(), Anchor::Custom(Vec2::new(0., -0.25))),
+        PropLook::Block => (default(), Anchor::Center),
+    };
+
+    commands.spawn((
+        SpriteBundle {
+            texture,
+            sprite: Sprite {
+                color: archetype.tint,
+                anchor,
+                custom_size: Some(archetype.size),
+                ..default()
+            },
+            transform: Transform::from_translation(vec3_translation),
+            ..default()
+        },
+        GameEntity,
+        Prop,
+        PropKind(archetype.id.clone()),
+        Collidable {
+            size: Transform::from_scale(archetype.collider.extend(1.))
+        },
+        Health {
+            max: archetype.max_health,
+            current: archetype.max_health,
+        },
+    ));
+}
@@ -0,0 +1,78 @@
+use bevy::prelude::*;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+use crate::AppState;
+use resource::{RunSeed, SimulationRng, SimulationTick};
+
+pub mod resource;
+
+// Gameplay advances in fixed steps of this length regardless of frame rate, which keeps runs reproducible.
+pub const SIMULATION_TICK_SECS: f32 = 1. / 60.;
+
+// Runs in `FixedUpdate` before every other gameplay system.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SimulationTickSet;
+
+pub struct SimulationPlugin;
+
+impl Plugin for SimulationPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(FixedTime::new_from_secs(SIMULATION_TICK_SECS))
+            .init_resource::<RunSeed>()
+            .init_resource::<SimulationTick>()
+            .insert_resource(SimulationRng(ChaCha8Rng::seed_from_u64(0)))
+            .add_systems(OnEnter(AppState::GamePlaying), start_simulation)
+            .add_systems(OnExit(AppState::GamePlaying), reroll_seed)
+            .add_systems(FixedUpdate, advance_tick.in_set(SimulationTickSet).run_if(in_state(AppState::GamePlaying)));
+    }
+}
+
+// For events only gameplay systems send and read. They're swapped out at the start of every tick rather than every
+// frame, so one sent late in a tick still reaches systems that read it early in the next, however many frames apart.
+pub fn add_simulation_event<T: Event>(app: &mut App) {
+    app.init_resource::<Events<T>>()
+        .add_systems(FixedUpdate, Events::<T>::update_system.in_set(SimulationTickSet));
+}
+
+fn start_simulation(mut commands: Commands, seed: Res<RunSeed>) {
+    info!("Starting run with seed {}", seed.0);
+    commands.insert_resource(SimulationRng(ChaCha8Rng::seed_from_u64(seed.0)));
+    commands.insert_resource(SimulationTick(0));
+}
+
+// Every run gets a fresh seed unless something (like a replay) picks one before it starts.
+fn reroll_seed(mut commands: Commands) {
+    commands.insert_resource(RunSeed::default());
+}
+
+fn advance_tick(mut tick: ResMut<SimulationTick>) {
+    tick.0 += 1;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Event)]
+    struct Bump;
+
+    #[test]
+    fn simulation_events_last_until_the_next_tick_is_over() {
+        let mut app = App::new();
+        add_simulation_event::<Bump>(&mut app);
+        app.world.send_event(Bump);
+
+        // Frames that don't run a tick leave the event alone.
+        for _ in 0..5 {
+            app.update();
+        }
+        assert_eq!(app.world.resource::<Events<Bump>>().len(), 1);
+
+        app.world.run_schedule(FixedUpdate);
+        assert_eq!(app.world.resource::<Events<Bump>>().len(), 1);
+
+        app.world.run_schedule(FixedUpdate);
+        assert!(app.world.resource::<Events<Bump>>().is_empty());
+    }
+}
@@ -0,0 +1,23 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use bevy::prelude::*;
+use rand_chacha::ChaCha8Rng;
+
+// The seed for the next run. Runs started with the same seed and the same inputs play out identically.
+#[derive(Resource)]
+pub struct RunSeed(pub u64);
+
+impl Default for RunSeed {
+    fn default() -> Self {
+        let nanos = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |duration| duration.as_nanos());
+        RunSeed(nanos as u64)
+    }
+}
+
+// The only source of randomness gameplay systems may use; reseeded from `RunSeed` at the start of every run.
+#[derive(Resource, Deref, DerefMut)]
+pub struct SimulationRng(pub ChaCha8Rng);
+
+// Number of fixed ticks simulated in the current run.
+#[derive(Resource, Default)]
+pub struct SimulationTick(pub u64);
@@ -11,4 +11,8 @@ pub enum FacingDirection {
 pub struct AnimationTimer(pub Timer);
 
 #[derive(Component)]
-pub struct Layered;
\ No newline at end of file
+pub struct Layered;
+
+// The sprite's own colour, which temporary tints (status effects, hit flashes) fall back to.
+#[derive(Component)]
+pub struct BaseColor(pub Color);
@@ -1,11 +1,13 @@
 use bevy::prelude::*;
 use crate::game::movement::component::Movable;
-use crate::game::sprite::component::{AnimationTimer, FacingDirection, Layered};
+use crate::game::feedback::component::HitFlash;
+use crate::game::sprite::component::{AnimationTimer, BaseColor, FacingDirection, Layered};
+use crate::game::status::component::StatusEffects;
 
 //Handles the animation switching for Movable, AnimationTimer TextureAtlas sprites.
 // TODO: move the actual animation component out of this so it will still apply to static animated sprites; separate as "sprite animation system"
 pub fn animate_sprite(
-    time: Res<Time>,
+    fixed_time: Res<FixedTime>,
     texture_atlases: Res<Assets<TextureAtlas>>,
     mut movable_sprite_entity: Query<(
         &mut Movable,
@@ -15,8 +17,10 @@ pub fn animate_sprite(
         &Handle<TextureAtlas>,
     )>,
 ) {
+    let _span = info_span!("animation").entered();
+
     for (movable_struct, mut facing_direction, mut timer, mut sprite, texture_atlas_handle) in &mut movable_sprite_entity {
-        timer.tick(time.delta());
+        timer.tick(fixed_time.period);
         if timer.just_finished() {
             if movable_struct.get_x_direction().gt(&0.) {
                 *facing_direction = FacingDirection::East
@@ -50,11 +54,42 @@ pub fn animate_sprite(
 pub fn layering_system(
     mut sprite_entities: Query<&mut Transform, (With<Movable>, With<Layered>)>,
 ) {
+    let _span = info_span!("layering").entered();
+
      for mut transform in &mut sprite_entities {
         let next_translation = 0.5 - (transform.translation.y / 2000.);
 
         transform.translation.z = next_translation;
-
-        // info!("layering: <{},{},{}>", transform.translation.x, transform.translation.y, transform.translation.z);
      }
-}
\ No newline at end of file
+}
+// Works out the colour of every tintable sprite: a running hit flash wins over status effect tints,
+// which in turn win over the sprite's own colour.
+#[allow(clippy::type_complexity)]
+pub fn sprite_color_system(
+    mut sprites: Query<
+        (&BaseColor, Option<&StatusEffects>, Option<&HitFlash>, &mut Sprite),
+        Or<(Changed<StatusEffects>, Changed<HitFlash>)>,
+    >,
+    mut atlas_sprites: Query<
+        (&BaseColor, Option<&StatusEffects>, Option<&HitFlash>, &mut TextureAtlasSprite),
+        (Or<(Changed<StatusEffects>, Changed<HitFlash>)>, Without<Sprite>),
+    >,
+) {
+    for (base_color, status_effects, hit_flash, mut sprite) in &mut sprites {
+        sprite.color = current_color(base_color, status_effects, hit_flash);
+    }
+
+    for (base_color, status_effects, hit_flash, mut sprite) in &mut atlas_sprites {
+        sprite.color = current_color(base_color, status_effects, hit_flash);
+    }
+}
+
+fn current_color(base_color: &BaseColor, status_effects: Option<&StatusEffects>, hit_flash: Option<&HitFlash>) -> Color {
+    if let Some(hit_flash) = hit_flash.filter(|hit_flash| hit_flash.remaining_secs > 0.) {
+        return hit_flash.color;
+    }
+
+    status_effects
+        .and_then(StatusEffects::tint)
+        .unwrap_or(base_color.0)
+}
@@ -0,0 +1,161 @@
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use serde::{Deserialize, Serialize};
+
+use crate::game::passive::component::PassiveKind;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum Stat {
+    MaxHealth,
+    MoveSpeed,
+    // Multiplier on all weapon damage; base 1.0.
+    Damage,
+    PickupRadius,
+}
+
+// Where a modifier came from, so everything from one source can be removed again in one go.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum ModifierSource {
+    Shop,
+    Passive(PassiveKind),
+    StatusEffects,
+    Chest,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ModifierValue {
+    // Added to the base value.
+    Flat(f32),
+    // Fraction of the (flat modified) value; all percentages are summed before being applied, so +10% twice is +20%.
+    Percent(f32),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StatModifier {
+    pub stat: Stat,
+    pub source: ModifierSource,
+    pub value: ModifierValue,
+}
+
+// Base stats plus every modifier currently affecting them. The final values are recalculated whenever
+// the modifiers change, and synced onto components like `Movable` and `Health` by `stats_sync_system`.
+#[derive(Component, Default, Debug)]
+pub struct Stats {
+    base: HashMap<Stat, f32>,
+    modifiers: Vec<StatModifier>,
+    values: HashMap<Stat, f32>,
+}
+
+impl Stats {
+    pub fn new(base: impl IntoIterator<Item = (Stat, f32)>) -> Self {
+        let mut stats = Stats {
+            base: base.into_iter().collect(),
+            ..default()
+        };
+        stats.recalculate();
+
+        stats
+    }
+
+    pub fn get(&self, stat: Stat) -> f32 {
+        self.values.get(&stat).copied().unwrap_or(0.)
+    }
+
+    pub fn base(&self, stat: Stat) -> f32 {
+        self.base.get(&stat).copied().unwrap_or(0.)
+    }
+
+    pub fn set_base(&mut self, stat: Stat, value: f32) {
+        self.base.insert(stat, value);
+        self.recalculate();
+    }
+
+    pub fn add_modifier(&mut self, modifier: StatModifier) {
+        self.modifiers.push(modifier);
+        self.recalculate();
+    }
+
+    // Returns whether anything was removed.
+    pub fn remove_modifiers(&mut self, source: ModifierSource) -> bool {
+        let count = self.modifiers.len();
+        self.modifiers.retain(|modifier| modifier.source != source);
+
+        if self.modifiers.len() == count {
+            return false;
+        }
+
+        self.recalculate();
+        true
+    }
+
+    pub fn modifiers(&self) -> &[StatModifier] {
+        &self.modifiers
+    }
+
+    fn recalculate(&mut self) {
+        self.values.clear();
+
+        for (stat, base) in &self.base {
+            let mut flat = 0.;
+            let mut percent = 0.;
+
+            for modifier in self.modifiers.iter().filter(|modifier| modifier.stat == *stat) {
+                match modifier.value {
+                    ModifierValue::Flat(value) => flat += value,
+                    ModifierValue::Percent(value) => percent += value,
+                }
+            }
+
+            self.values.insert(*stat, ((base + flat) * (1. + percent)).max(0.));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn modifier(stat: Stat, source: ModifierSource, value: ModifierValue) -> StatModifier {
+        StatModifier { stat, source, value }
+    }
+
+    #[test]
+    fn flat_modifiers_apply_before_percentages() {
+        let mut stats = Stats::new([(Stat::MaxHealth, 100.)]);
+        stats.add_modifier(modifier(Stat::MaxHealth, ModifierSource::Shop, ModifierValue::Percent(0.1)));
+        stats.add_modifier(modifier(Stat::MaxHealth, ModifierSource::Shop, ModifierValue::Percent(0.1)));
+        stats.add_modifier(modifier(Stat::MaxHealth, ModifierSource::StatusEffects, ModifierValue::Flat(20.)));
+
+        assert!((stats.get(Stat::MaxHealth) - 144.).abs() < 0.001);
+    }
+
+    #[test]
+    fn removing_a_source_restores_the_value() {
+        let mut stats = Stats::new([(Stat::MoveSpeed, 200.)]);
+        stats.add_modifier(modifier(Stat::MoveSpeed, ModifierSource::Shop, ModifierValue::Percent(0.25)));
+        stats.add_modifier(modifier(Stat::MoveSpeed, ModifierSource::StatusEffects, ModifierValue::Percent(-0.5)));
+        assert!((stats.get(Stat::MoveSpeed) - 150.).abs() < 0.001);
+
+        assert!(stats.remove_modifiers(ModifierSource::StatusEffects));
+        assert!((stats.get(Stat::MoveSpeed) - 250.).abs() < 0.001);
+        assert!(!stats.remove_modifiers(ModifierSource::StatusEffects));
+    }
+
+    #[test]
+    fn values_never_go_negative() {
+        let mut stats = Stats::new([(Stat::MoveSpeed, 200.)]);
+        stats.add_modifier(modifier(Stat::MoveSpeed, ModifierSource::StatusEffects, ModifierValue::Percent(-1.5)));
+
+        assert_eq!(stats.get(Stat::MoveSpeed), 0.);
+    }
+
+    #[test]
+    fn modifiers_only_affect_their_own_stat() {
+        let mut stats = Stats::new([(Stat::MaxHealth, 100.)]);
+        stats.add_modifier(modifier(Stat::MaxHealth, ModifierSource::Shop, ModifierValue::Percent(0.2)));
+        stats.add_modifier(modifier(Stat::MoveSpeed, ModifierSource::Shop, ModifierValue::Percent(0.2)));
+
+        assert!((stats.get(Stat::MaxHealth) - 120.).abs() < 0.001);
+        assert_eq!(stats.get(Stat::MoveSpeed), 0.);
+    }
+}
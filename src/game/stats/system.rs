@@ -0,0 +1,78 @@
+use bevy::prelude::*;
+
+use crate::game::health::component::Health;
+use crate::game::movement::component::Movable;
+use crate::game::stats::component::{ModifierSource, ModifierValue, Stat, StatModifier, Stats};
+use crate::game::status::component::StatusEffects;
+
+// Mirrors the movement effects of active status effects as a single `MoveSpeed` modifier.
+pub fn status_stat_modifier_system(
+    mut afflicted: Query<(&StatusEffects, &mut Stats), Changed<StatusEffects>>,
+) {
+    for (status_effects, mut stats) in &mut afflicted {
+        let percent = status_effects.speed_multiplier() - 1.;
+        let current = stats.modifiers().iter()
+            .find(|modifier| modifier.source == ModifierSource::StatusEffects)
+            .map(|modifier| modifier.value);
+
+        // Status effects tick every frame; only touch `Stats` when the outcome differs so syncing stays rare.
+        let desired = (percent != 0.).then_some(ModifierValue::Percent(percent));
+        if current == desired {
+            continue;
+        }
+
+        stats.remove_modifiers(ModifierSource::StatusEffects);
+        if let Some(value) = desired {
+            stats.add_modifier(StatModifier { stat: Stat::MoveSpeed, source: ModifierSource::StatusEffects, value });
+        }
+    }
+}
+
+// Pushes recalculated stats onto the components the rest of the game reads.
+#[allow(clippy::type_complexity)]
+pub fn stats_sync_system(
+    mut changed: Query<(&Stats, Option<&mut Movable>, Option<&mut Health>), Changed<Stats>>,
+) {
+    for (stats, movable, health) in &mut changed {
+        if let Some(mut movable) = movable {
+            movable.velocity = stats.get(Stat::MoveSpeed);
+        }
+
+        if let Some(mut health) = health {
+            let max_health = stats.get(Stat::MaxHealth);
+            if max_health != health.max {
+                // Gaining max health heals by the same amount, losing it only caps current health.
+                let gained = (max_health - health.max).max(0.);
+                health.max = max_health;
+                health.current = (health.current + gained).min(max_health);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn changed_stats_sync_onto_components() {
+        let mut world = World::new();
+        let mut stats = Stats::new([(Stat::MoveSpeed, 200.), (Stat::MaxHealth, 100.)]);
+        stats.add_modifier(StatModifier { stat: Stat::MaxHealth, source: ModifierSource::Shop, value: ModifierValue::Percent(0.5) });
+
+        let entity = world.spawn((
+            stats,
+            Movable { velocity: 0., direction: Vec2::ZERO },
+            Health { max: 100., current: 40. },
+        )).id();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(stats_sync_system);
+        schedule.run(&mut world);
+
+        assert_eq!(world.get::<Movable>(entity).unwrap().velocity, 200.);
+        let health = world.get::<Health>(entity).unwrap();
+        assert_eq!(health.max, 150.);
+        assert_eq!(health.current, 90.);
+    }
+}
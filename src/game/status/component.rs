@@ -0,0 +1,192 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum StatusEffectKind {
+    // Reduces movement speed by `strength` (0.3 is 30% slower).
+    Slow,
+    // Deals `strength` damage per second.
+    Burn,
+    // Deals `strength` damage per second per stack.
+    Poison,
+    // Stops all movement.
+    Freeze,
+    // Stops all movement; unlike freeze, reapplying never shortens or refreshes a longer stun.
+    Stun,
+}
+
+// Poison is the only effect that stacks in intensity.
+const MAX_POISON_STACKS: u32 = 5;
+
+impl StatusEffectKind {
+    pub fn tint(&self) -> Color {
+        match self {
+            StatusEffectKind::Slow => Color::rgb(0.6, 0.6, 0.9),
+            StatusEffectKind::Burn => Color::rgb(1., 0.5, 0.2),
+            StatusEffectKind::Poison => Color::rgb(0.4, 1., 0.4),
+            StatusEffectKind::Freeze => Color::rgb(0.5, 0.9, 1.),
+            StatusEffectKind::Stun => Color::rgb(1., 1., 0.4),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StatusEffect {
+    pub kind: StatusEffectKind,
+    pub strength: f32,
+    pub remaining_secs: f32,
+    pub stacks: u32,
+}
+
+impl StatusEffect {
+    pub fn new(kind: StatusEffectKind, strength: f32, duration_secs: f32) -> Self {
+        StatusEffect {
+            kind,
+            strength,
+            remaining_secs: duration_secs,
+            stacks: 1,
+        }
+    }
+
+    pub fn damage_per_second(&self) -> f32 {
+        match self.kind {
+            StatusEffectKind::Burn => self.strength,
+            StatusEffectKind::Poison => self.strength * self.stacks as f32,
+            _ => 0.,
+        }
+    }
+}
+
+#[derive(Component, Default)]
+pub struct StatusEffects {
+    pub effects: Vec<StatusEffect>,
+    // Damage over time accumulated since the last damage tick.
+    pub pending_damage: f32,
+    pub damage_tick_secs: f32,
+}
+
+impl StatusEffects {
+    // Applies an effect following its stacking rules; at most one effect of each kind is ever kept.
+    pub fn apply(&mut self, effect: StatusEffect) {
+        let Some(existing) = self.effects.iter_mut().find(|existing| existing.kind == effect.kind) else {
+            self.effects.push(effect);
+            return;
+        };
+
+        match effect.kind {
+            StatusEffectKind::Poison => {
+                existing.stacks = (existing.stacks + effect.stacks).min(MAX_POISON_STACKS);
+                existing.strength = existing.strength.max(effect.strength);
+                existing.remaining_secs = effect.remaining_secs;
+            },
+            StatusEffectKind::Stun => {
+                existing.remaining_secs = existing.remaining_secs.max(effect.remaining_secs);
+            },
+            StatusEffectKind::Slow | StatusEffectKind::Burn | StatusEffectKind::Freeze => {
+                existing.strength = existing.strength.max(effect.strength);
+                existing.remaining_secs = effect.remaining_secs;
+            },
+        }
+    }
+
+    // Counts all effects down, dropping the ones that have run out.
+    pub fn tick(&mut self, delta_secs: f32) {
+        for effect in &mut self.effects {
+            effect.remaining_secs -= delta_secs;
+        }
+
+        self.effects.retain(|effect| effect.remaining_secs > 0.);
+    }
+
+    pub fn get(&self, kind: StatusEffectKind) -> Option<&StatusEffect> {
+        self.effects.iter().find(|effect| effect.kind == kind)
+    }
+
+    // Factor to scale movement velocity by; 0 while frozen or stunned.
+    pub fn speed_multiplier(&self) -> f32 {
+        if self.get(StatusEffectKind::Freeze).is_some() || self.get(StatusEffectKind::Stun).is_some() {
+            return 0.;
+        }
+
+        match self.get(StatusEffectKind::Slow) {
+            Some(slow) => (1. - slow.strength).clamp(0., 1.),
+            None => 1.,
+        }
+    }
+
+    pub fn damage_per_second(&self) -> f32 {
+        self.effects.iter().map(StatusEffect::damage_per_second).sum()
+    }
+
+    // The most recently applied effect decides the tint.
+    pub fn tint(&self) -> Option<Color> {
+        self.effects.last().map(|effect| effect.kind.tint())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn poison_stacks_up_to_the_limit() {
+        let mut status_effects = StatusEffects::default();
+
+        for _ in 0..(MAX_POISON_STACKS + 3) {
+            status_effects.apply(StatusEffect::new(StatusEffectKind::Poison, 2., 3.));
+        }
+
+        let poison = status_effects.get(StatusEffectKind::Poison).unwrap();
+        assert_eq!(poison.stacks, MAX_POISON_STACKS);
+        assert_eq!(status_effects.damage_per_second(), 2. * MAX_POISON_STACKS as f32);
+        assert_eq!(status_effects.effects.len(), 1);
+    }
+
+    #[test]
+    fn reapplying_refreshes_duration_and_keeps_strongest() {
+        let mut status_effects = StatusEffects::default();
+        status_effects.apply(StatusEffect::new(StatusEffectKind::Slow, 0.5, 2.));
+        status_effects.tick(1.5);
+        status_effects.apply(StatusEffect::new(StatusEffectKind::Slow, 0.2, 2.));
+
+        let slow = status_effects.get(StatusEffectKind::Slow).unwrap();
+        assert_eq!(slow.remaining_secs, 2.);
+        assert_eq!(slow.strength, 0.5);
+        assert_eq!(slow.stacks, 1);
+    }
+
+    #[test]
+    fn stun_is_never_shortened() {
+        let mut status_effects = StatusEffects::default();
+        status_effects.apply(StatusEffect::new(StatusEffectKind::Stun, 1., 3.));
+        status_effects.apply(StatusEffect::new(StatusEffectKind::Stun, 1., 0.5));
+
+        assert_eq!(status_effects.get(StatusEffectKind::Stun).unwrap().remaining_secs, 3.);
+    }
+
+    #[test]
+    fn effects_expire() {
+        let mut status_effects = StatusEffects::default();
+        status_effects.apply(StatusEffect::new(StatusEffectKind::Burn, 4., 1.));
+        status_effects.apply(StatusEffect::new(StatusEffectKind::Freeze, 1., 3.));
+
+        status_effects.tick(1.);
+        assert!(status_effects.get(StatusEffectKind::Burn).is_none());
+        assert_eq!(status_effects.speed_multiplier(), 0.);
+
+        status_effects.tick(2.);
+        assert!(status_effects.effects.is_empty());
+        assert_eq!(status_effects.speed_multiplier(), 1.);
+        assert_eq!(status_effects.tint(), None);
+    }
+
+    #[test]
+    fn freeze_overrides_slow() {
+        let mut status_effects = StatusEffects::default();
+        status_effects.apply(StatusEffect::new(StatusEffectKind::Slow, 0.3, 2.));
+        assert!((status_effects.speed_multiplier() - 0.7).abs() < f32::EPSILON);
+
+        status_effects.apply(StatusEffect::new(StatusEffectKind::Freeze, 1., 2.));
+        assert_eq!(status_effects.speed_multiplier(), 0.);
+    }
+}
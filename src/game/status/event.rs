@@ -0,0 +1,10 @@
+use bevy::prelude::*;
+
+use crate::game::status::component::StatusEffect;
+
+// Sent by anything that wants to afflict an entity, e.g. weapon hits or enemy attacks.
+#[derive(Event)]
+pub struct ApplyStatusEvent {
+    pub target: Entity,
+    pub effect: StatusEffect,
+}
@@ -0,0 +1,151 @@
+use bevy::prelude::*;
+
+use crate::game::health::event::DamageEvent;
+use crate::game::status::component::StatusEffects;
+use crate::game::status::event::ApplyStatusEvent;
+
+// Damage over time is dealt in chunks rather than every frame.
+const DAMAGE_TICK_SECS: f32 = 0.5;
+
+pub fn apply_status_event_system(
+    mut commands: Commands,
+    mut status_events: EventReader<ApplyStatusEvent>,
+    mut afflictable: Query<&mut StatusEffects>,
+) {
+    for event in status_events.iter() {
+        match afflictable.get_mut(event.target) {
+            Ok(mut status_effects) => status_effects.apply(event.effect),
+            Err(_) => {
+                let mut status_effects = StatusEffects::default();
+                status_effects.apply(event.effect);
+
+                if let Some(mut entity_commands) = commands.get_entity(event.target) {
+                    entity_commands.insert(status_effects);
+                }
+            }
+        }
+    }
+}
+
+// Counts effects down and turns burn/poison into damage every `DAMAGE_TICK_SECS`.
+pub fn status_effect_tick_system(
+    fixed_time: Res<FixedTime>,
+    mut afflicted: Query<(Entity, &mut StatusEffects)>,
+    mut damage_events: EventWriter<DamageEvent>,
+) {
+    let delta_secs = fixed_time.period.as_secs_f32();
+
+    for (entity, mut status_effects) in &mut afflicted {
+        if status_effects.effects.is_empty() && status_effects.pending_damage <= 0. {
+            continue;
+        }
+
+        let damage = status_effects.damage_per_second() * delta_secs;
+        status_effects.pending_damage += damage;
+        status_effects.damage_tick_secs += delta_secs;
+        status_effects.tick(delta_secs);
+
+        let tick_finished = status_effects.damage_tick_secs >= DAMAGE_TICK_SECS;
+        if (tick_finished || status_effects.effects.is_empty()) && status_effects.pending_damage > 0. {
+            damage_events.send(DamageEvent { target: entity, amount: status_effects.pending_damage });
+            status_effects.pending_damage = 0.;
+        }
+
+        if tick_finished {
+            status_effects.damage_tick_secs -= DAMAGE_TICK_SECS;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::health::component::Health;
+    use crate::game::health::system::apply_damage_system;
+    use crate::game::level::component::LevelFloor;
+    use crate::game::movement::{movement_systems, MovementSet};
+    use crate::game::movement::component::{Collidable, Movable, MoveStep};
+    use crate::game::movement::resource::StaticColliders;
+    use crate::game::stats::component::{Stat, Stats};
+    use crate::game::stats::system::{stats_sync_system, status_stat_modifier_system};
+    use crate::game::status::component::{StatusEffect, StatusEffectKind};
+
+    fn world_with_level() -> World {
+        let mut world = World::new();
+        world.insert_resource(FixedTime::new_from_secs(0.1));
+        world.init_resource::<StaticColliders>();
+        world.spawn((
+            Transform::default(),
+            Sprite {
+                custom_size: Some(Vec2::new(5000., 2000.)),
+                ..default()
+            },
+            LevelFloor,
+        ));
+
+        world
+    }
+
+    fn spawn_walker(world: &mut World, effect: Option<StatusEffect>) -> Entity {
+        let mut status_effects = StatusEffects::default();
+        if let Some(effect) = effect {
+            status_effects.apply(effect);
+        }
+
+        world.spawn((
+            Transform::default(),
+            Movable { velocity: 100., direction: Vec2::X },
+            MoveStep::default(),
+            Collidable { size: Transform::from_scale(Vec3::new(10., 10., 1.)) },
+            Stats::new([(Stat::MoveSpeed, 100.)]),
+            status_effects,
+        )).id()
+    }
+
+    fn x_after_moving(effect: Option<StatusEffect>) -> f32 {
+        let mut world = world_with_level();
+        let walker = spawn_walker(&mut world, effect);
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(((status_stat_modifier_system, stats_sync_system).chain().before(MovementSet), movement_systems()));
+        schedule.run(&mut world);
+
+        world.get::<Transform>(walker).unwrap().translation.x
+    }
+
+    #[test]
+    fn frozen_entities_do_not_move() {
+        assert!((x_after_moving(None) - 10.).abs() < 0.001);
+        assert_eq!(x_after_moving(Some(StatusEffect::new(StatusEffectKind::Freeze, 1., 1.))), 0.);
+        assert_eq!(x_after_moving(Some(StatusEffect::new(StatusEffectKind::Stun, 1., 1.))), 0.);
+    }
+
+    #[test]
+    fn slowed_entities_move_slower() {
+        let x = x_after_moving(Some(StatusEffect::new(StatusEffectKind::Slow, 0.5, 1.)));
+
+        assert!((x - 5.).abs() < 0.001);
+    }
+
+    #[test]
+    fn burn_ticks_damage_into_health() {
+        let mut world = World::new();
+        world.insert_resource(FixedTime::new_from_secs(DAMAGE_TICK_SECS));
+        world.init_resource::<Events<DamageEvent>>();
+        world.init_resource::<Events<ApplyStatusEvent>>();
+
+        let target = world.spawn(Health { max: 100., current: 100. }).id();
+        world.send_event(ApplyStatusEvent {
+            target,
+            effect: StatusEffect::new(StatusEffectKind::Burn, 10., 5.),
+        });
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems((apply_status_event_system, status_effect_tick_system, apply_damage_system).chain());
+        // The first run only inserts the missing `StatusEffects` component.
+        schedule.run(&mut world);
+        schedule.run(&mut world);
+
+        assert!((world.get::<Health>(target).unwrap().current - 95.).abs() < 0.001);
+    }
+}
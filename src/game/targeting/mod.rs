@@ -0,0 +1,38 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use rand::Rng;
+
+use resource::{EnemyIndex, Target};
+
+pub mod resource;
+pub mod system;
+
+// Keeps `EnemyIndex` up to date; everything using `Targeting` runs after it.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TargetingSet;
+
+// What weapons and abilities ask when they need to pick enemies to go for. Answers come from `EnemyIndex`, so
+// systems using this should run after `TargetingSet`. It only reads, so any number of those can run side by side;
+// the ones picking at random bring their own `SimulationRng`.
+#[derive(SystemParam)]
+pub struct Targeting<'w> {
+    index: Res<'w, EnemyIndex>,
+}
+
+impl Targeting<'_> {
+    pub fn nearest(&self, position: Vec2) -> Option<Target> {
+        self.index.nearest(position)
+    }
+
+    pub fn nearest_n(&self, position: Vec2, count: usize) -> Vec<Target> {
+        self.index.nearest_n(position, count)
+    }
+
+    pub fn random_within(&self, center: Vec2, radius: f32, rng: &mut impl Rng) -> Option<Target> {
+        self.index.random_within(center, radius, rng)
+    }
+
+    pub fn densest_cluster(&self, center: Vec2, search_radius: f32, cluster_radius: f32) -> Option<Vec2> {
+        self.index.densest_cluster(center, search_radius, cluster_radius)
+    }
+}
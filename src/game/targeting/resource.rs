@@ -0,0 +1,215 @@
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use rand::Rng;
+
+// About two enemies across, so a weapon's reach only spans a few cells.
+const CELL_SIZE: f32 = 64.;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Target {
+    pub entity: Entity,
+    pub position: Vec2,
+}
+
+// Where every enemy stood at the end of the last movement step, bucketed into a grid so weapons only look at
+// the enemies around the spot they're interested in. Rebuilt every tick.
+#[derive(Resource, Default)]
+pub struct EnemyIndex {
+    cells: HashMap<IVec2, Vec<Target>>,
+    min: IVec2,
+    max: IVec2,
+    len: usize,
+}
+
+fn cell_of(position: Vec2) -> IVec2 {
+    (position / CELL_SIZE).floor().as_ivec2()
+}
+
+impl EnemyIndex {
+    pub fn rebuild(&mut self, targets: impl IntoIterator<Item = Target>) {
+        // Keeps the buckets that were in use last tick around, since most of them will be again.
+        self.cells.retain(|_, cell| !cell.is_empty());
+        self.cells.values_mut().for_each(Vec::clear);
+        self.min = IVec2::MAX;
+        self.max = IVec2::MIN;
+        self.len = 0;
+
+        for target in targets {
+            let cell = cell_of(target.position);
+            self.min = self.min.min(cell);
+            self.max = self.max.max(cell);
+            self.len += 1;
+            self.cells.entry(cell).or_default().push(target);
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn nearest(&self, position: Vec2) -> Option<Target> {
+        self.nearest_n(position, 1).first().copied()
+    }
+
+    // Up to `count` enemies, closest first. Searches outwards ring by ring and stops once no unvisited cell can
+    // hold anything closer than the furthest of the ones found.
+    pub fn nearest_n(&self, position: Vec2, count: usize) -> Vec<Target> {
+        let mut found: Vec<(f32, Target)> = Vec::new();
+        if count == 0 || self.is_empty() {
+            return Vec::new();
+        }
+
+        let center = cell_of(position);
+        // Rings that miss every occupied cell can be skipped outright.
+        let first_ring = (self.min - center).max(center - self.max).max_element().max(0);
+        let last_ring = (self.max - center).max(center - self.min).max_element().max(0);
+
+        for ring in first_ring..=last_ring {
+            if found.len() >= count {
+                let furthest = found[count - 1].0;
+                if ((ring - 1) as f32 * CELL_SIZE).powi(2) >= furthest {
+                    break;
+                }
+            }
+
+            for cell in ring_cells(center, ring) {
+                let Some(targets) = self.cells.get(&cell) else { continue };
+                found.extend(targets.iter().map(|target| (target.position.distance_squared(position), *target)));
+            }
+
+            // A stable sort keeps ties in the order enemies were added, so the same run always picks the same one.
+            found.sort_by(|a, b| a.0.total_cmp(&b.0));
+            found.truncate(count);
+        }
+
+        found.into_iter().map(|(_, target)| target).collect()
+    }
+
+    pub fn within(&self, center: Vec2, radius: f32) -> impl Iterator<Item = &Target> {
+        let min = cell_of(center - Vec2::splat(radius));
+        let max = cell_of(center + Vec2::splat(radius));
+
+        (min.y..=max.y)
+            .flat_map(move |y| (min.x..=max.x).map(move |x| IVec2::new(x, y)))
+            .filter_map(|cell| self.cells.get(&cell))
+            .flatten()
+            .filter(move |target| target.position.distance_squared(center) <= radius * radius)
+    }
+
+    pub fn random_within(&self, center: Vec2, radius: f32, rng: &mut impl Rng) -> Option<Target> {
+        let targets: Vec<_> = self.within(center, radius).collect();
+        if targets.is_empty() {
+            return None;
+        }
+
+        Some(*targets[rng.gen_range(0..targets.len())])
+    }
+
+    // The middle of the biggest group of enemies within `search_radius`: of every enemy there, the one with the
+    // most others within `cluster_radius` of it, and then the average position of that group.
+    pub fn densest_cluster(&self, center: Vec2, search_radius: f32, cluster_radius: f32) -> Option<Vec2> {
+        let mut densest: Option<(usize, Vec2)> = None;
+
+        for candidate in self.within(center, search_radius) {
+            let (count, sum) = self.within(candidate.position, cluster_radius)
+                .fold((0, Vec2::ZERO), |(count, sum), neighbour| (count + 1, sum + neighbour.position));
+
+            if densest.is_none_or(|(most, _)| count > most) {
+                densest = Some((count, sum / count as f32));
+            }
+        }
+
+        densest.map(|(_, middle)| middle)
+    }
+}
+
+fn ring_cells(center: IVec2, ring: i32) -> impl Iterator<Item = IVec2> {
+    (-ring..=ring).flat_map(move |y| {
+        // Only the edges of the square; the inside was covered by earlier rings.
+        let step = if y.abs() == ring { 1 } else { (2 * ring).max(1) as usize };
+        (-ring..=ring).step_by(step).map(move |x| center + IVec2::new(x, y))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    use super::*;
+
+    fn scattered(count: u32) -> Vec<Target> {
+        let mut rng = ChaCha8Rng::seed_from_u64(7);
+        (0..count)
+            .map(|i| Target {
+                entity: Entity::from_raw(i),
+                position: Vec2::new(rng.gen_range(-800. ..800.), rng.gen_range(-500. ..500.)),
+            })
+            .collect()
+    }
+
+    fn index_of(targets: &[Target]) -> EnemyIndex {
+        let mut index = EnemyIndex::default();
+        index.rebuild(targets.iter().copied());
+        index
+    }
+
+    fn by_distance(targets: &[Target], position: Vec2) -> Vec<Target> {
+        let mut sorted = targets.to_vec();
+        sorted.sort_by(|a, b| a.position.distance_squared(position).total_cmp(&b.position.distance_squared(position)));
+        sorted
+    }
+
+    #[test]
+    fn finds_the_same_nearest_enemies_as_checking_them_all() {
+        let targets = scattered(300);
+        let index = index_of(&targets);
+
+        for position in [Vec2::ZERO, Vec2::new(790., -490.), Vec2::new(-2000., 1500.), Vec2::new(33., 260.)] {
+            let expected = by_distance(&targets, position);
+
+            assert_eq!(index.nearest(position), Some(expected[0]));
+            assert_eq!(index.nearest_n(position, 10), expected[..10]);
+        }
+
+        assert_eq!(index.nearest_n(Vec2::ZERO, 500).len(), 300);
+        assert_eq!(EnemyIndex::default().nearest(Vec2::ZERO), None);
+    }
+
+    #[test]
+    fn only_picks_random_enemies_within_the_radius() {
+        let targets = scattered(300);
+        let index = index_of(&targets);
+        let center = Vec2::new(100., 50.);
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+
+        let inside = targets.iter().filter(|target| target.position.distance(center) <= 150.).count();
+        assert_eq!(index.within(center, 150.).count(), inside);
+
+        for _ in 0..100 {
+            let target = index.random_within(center, 150., &mut rng).unwrap();
+            assert!(target.position.distance(center) <= 150.);
+        }
+
+        assert_eq!(index.random_within(Vec2::new(5000., 0.), 150., &mut rng), None);
+    }
+
+    #[test]
+    fn finds_the_middle_of_the_densest_cluster() {
+        let mut targets = scattered(40);
+        let cluster = Vec2::new(-300., 200.);
+        targets.extend((0..12).map(|i| Target {
+            entity: Entity::from_raw(100 + i),
+            position: cluster + Vec2::from_angle(i as f32) * 10.,
+        }));
+        let index = index_of(&targets);
+
+        let middle = index.densest_cluster(Vec2::ZERO, 1000., 40.).unwrap();
+        assert!(middle.distance(cluster) < 10., "{} is not near {}", middle, cluster);
+        assert_eq!(index.densest_cluster(Vec2::new(5000., 0.), 100., 40.), None);
+    }
+}
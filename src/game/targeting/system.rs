@@ -0,0 +1,11 @@
+use bevy::prelude::*;
+
+use crate::game::enemy::component::Enemy;
+use crate::game::targeting::resource::{EnemyIndex, Target};
+
+// Runs before movement, like the weapons asking it, so they aim at where enemies stand at the start of the tick.
+pub fn enemy_index_system(mut index: ResMut<EnemyIndex>, enemies: Query<(Entity, &Transform), With<Enemy>>) {
+    let _span = info_span!("targeting").entered();
+
+    index.rebuild(enemies.iter().map(|(entity, transform)| Target { entity, position: transform.translation.truncate() }));
+}
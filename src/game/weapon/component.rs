@@ -0,0 +1,271 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::game::pool::resource::PoolKind;
+use crate::game::weapon::evolution::Evolution;
+use crate::game::status::component::{StatusEffect, StatusEffectKind};
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum WeaponKind {
+    Knife,
+    Axe,
+    Whip,
+    Blades,
+    Halo,
+    Firebomb,
+    // Evolved weapons, only ever obtained through an `Evolution`.
+    Flurry,
+    Cleaver,
+}
+
+// Defined in `assets/data/game.weapons.ron`.
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+pub struct WeaponStats {
+    #[serde(default)]
+    pub behavior: WeaponBehavior,
+    pub damage: f32,
+    pub cooldown_secs: f32,
+    // Only for behaviors that fire projectiles, which can't do without it.
+    #[serde(default)]
+    pub projectile: Option<ProjectileStats>,
+    pub knockback: f32,
+    // Applied to every enemy the weapon hits.
+    pub status_effect: Option<OnHitStatus>,
+    // Weapons start at rank 1 and gain a rank from chests, each adding `damage_per_rank` times the base damage.
+    pub max_rank: u32,
+    pub damage_per_rank: f32,
+    #[serde(default)]
+    pub evolution: Option<Evolution>,
+}
+
+impl WeaponStats {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.cooldown_secs <= 0. {
+            return Err("cooldown must be positive".to_string());
+        }
+
+        if self.damage < 0. || self.knockback < 0. {
+            return Err("damage and knockback can't be negative".to_string());
+        }
+
+        match (self.behavior.fires_projectiles(), &self.projectile) {
+            (true, Some(projectile)) => projectile.validate()?,
+            (true, None) => return Err(format!("{:?} weapons need a projectile", self.behavior)),
+            (false, Some(_)) => return Err(format!("{:?} weapons don't fire projectiles", self.behavior)),
+            (false, None) => {}
+        }
+
+        if self.max_rank == 0 || self.damage_per_rank < 0. {
+            return Err("weapons need at least one rank and a non-negative damage per rank".to_string());
+        }
+
+        self.behavior.validate()
+    }
+
+    pub fn damage_at(&self, rank: u32) -> f32 {
+        self.damage * (1. + self.damage_per_rank * (rank.clamp(1, self.max_rank) - 1) as f32)
+    }
+}
+
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct ProjectileStats {
+    pub speed: f32,
+    pub size: Vec2,
+    pub lifetime_secs: f32,
+}
+
+impl ProjectileStats {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.speed < 0. || self.lifetime_secs <= 0. || self.size.min_element() <= 0. {
+            return Err("projectiles need a non-negative speed, a positive lifetime and a positive size".to_string());
+        }
+
+        Ok(())
+    }
+}
+
+// How a weapon attacks each time its cooldown comes round. Damage, knockback and the on-hit status effect
+// come from the rest of `WeaponStats` whatever the behavior.
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Default)]
+pub enum WeaponBehavior {
+    // A projectile flying straight at the nearest enemy.
+    #[default]
+    Projectile,
+    // A projectile that keeps turning towards the nearest enemy, by up to `turn_rate` radians a second.
+    Homing { turn_rate: f32 },
+    // A projectile that flies on to the next nearest enemy after a hit, `bounces` times.
+    Bouncing { bounces: u32 },
+    // `blades` projectiles of `size` circling the wielder until the next cooldown, each hitting an enemy once.
+    Orbit { blades: u32, radius: f32, angular_speed: f32, size: Vec2 },
+    // Damages every enemy within `radius` of the wielder, over and over.
+    Aura { radius: f32 },
+    // Lashes everything in a `range` by `width` strip in the wielder's facing direction.
+    Whip { range: f32, width: f32 },
+    // Leaves a patch under the nearest enemy that damages everything within `radius` for `duration_secs`.
+    Hazard { radius: f32, duration_secs: f32 },
+}
+
+impl WeaponBehavior {
+    // Whether the weapon fires `WeaponStats::projectile`; the others bring their own shapes.
+    pub fn fires_projectiles(&self) -> bool {
+        matches!(self, WeaponBehavior::Projectile | WeaponBehavior::Homing { .. } | WeaponBehavior::Bouncing { .. })
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        let valid = match *self {
+            WeaponBehavior::Projectile | WeaponBehavior::Bouncing { .. } => true,
+            WeaponBehavior::Homing { turn_rate } => turn_rate > 0.,
+            WeaponBehavior::Orbit { blades, radius, size, .. } => blades > 0 && radius > 0. && size.min_element() > 0.,
+            WeaponBehavior::Aura { radius } => radius > 0.,
+            WeaponBehavior::Whip { range, width } => range > 0. && width > 0.,
+            WeaponBehavior::Hazard { radius, duration_secs } => radius > 0. && duration_secs > 0.,
+        };
+
+        if !valid {
+            return Err(format!("{:?} needs positive counts and sizes", self));
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct OnHitStatus {
+    pub kind: StatusEffectKind,
+    pub strength: f32,
+    pub duration_secs: f32,
+}
+
+impl OnHitStatus {
+    pub fn effect(&self) -> StatusEffect {
+        StatusEffect::new(self.kind, self.strength, self.duration_secs)
+    }
+}
+
+impl WeaponKind {
+    pub const ALL: [WeaponKind; 8] = [
+        WeaponKind::Knife,
+        WeaponKind::Axe,
+        WeaponKind::Whip,
+        WeaponKind::Blades,
+        WeaponKind::Halo,
+        WeaponKind::Firebomb,
+        WeaponKind::Flurry,
+        WeaponKind::Cleaver,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            WeaponKind::Knife => "Knife",
+            WeaponKind::Axe => "Axe",
+            WeaponKind::Whip => "Whip",
+            WeaponKind::Blades => "Blades",
+            WeaponKind::Halo => "Halo",
+            WeaponKind::Firebomb => "Firebomb",
+            WeaponKind::Flurry => "Flurry",
+            WeaponKind::Cleaver => "Cleaver",
+        }
+    }
+
+    // The kinds an `Evolution` may turn a weapon into, and that nobody starts a run with.
+    pub fn is_evolved(&self) -> bool {
+        matches!(self, WeaponKind::Flurry | WeaponKind::Cleaver)
+    }
+}
+
+// Lives on a child entity of whoever wields it, so an entity can carry any number of weapons.
+#[derive(Component)]
+pub struct Weapon {
+    pub kind: WeaponKind,
+    pub rank: u32,
+    pub cooldown: Timer,
+}
+
+impl Weapon {
+    pub fn new(kind: WeaponKind, stats: &WeaponStats) -> Self {
+        Weapon {
+            kind,
+            rank: 1,
+            cooldown: Timer::from_seconds(stats.cooldown_secs, TimerMode::Repeating),
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct Projectile {
+    pub damage: f32,
+    pub velocity: Vec2,
+    pub size: Vec2,
+    pub lifetime: Timer,
+    pub knockback: f32,
+    pub status_effect: Option<StatusEffect>,
+}
+
+impl PoolKind for Projectile {
+    const NAME: &'static str = "projectile";
+    type Active = (Projectile, Homing, Bouncing, Orbiting, Piercing);
+}
+
+// Steers a projectile towards the nearest enemy.
+#[derive(Component)]
+pub struct Homing {
+    pub turn_rate: f32,
+}
+
+// Sends a projectile on to the next nearest target after a hit, while it has bounces left.
+#[derive(Component)]
+pub struct Bouncing {
+    pub remaining: u32,
+    pub last_hit: Option<Entity>,
+}
+
+// Keeps a projectile circling `wielder` instead of flying off.
+#[derive(Component)]
+pub struct Orbiting {
+    pub wielder: Entity,
+    pub angle: f32,
+    pub radius: f32,
+    pub angular_speed: f32,
+}
+
+// Lets a projectile carry on after a hit; it never hits the same target twice though.
+#[derive(Component, Default)]
+pub struct Piercing {
+    pub hit: Vec<Entity>,
+}
+
+// Damages everything within `radius` every time `interval` comes round, until `lifetime` runs out.
+// Zones that follow an entity stay centred on it.
+#[derive(Component)]
+pub struct DamageZone {
+    pub radius: f32,
+    pub damage: f32,
+    pub knockback: f32,
+    pub status_effect: Option<StatusEffect>,
+    pub interval: Timer,
+    pub lifetime: Timer,
+    pub follow: Option<Entity>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::resource::GameData;
+
+    #[test]
+    fn projectile_weapons_need_a_projectile() {
+        let mut stats = GameData::default().weapon(WeaponKind::Knife).clone();
+        stats.projectile = None;
+
+        assert_eq!(stats.validate(), Err("Projectile weapons need a projectile".to_string()));
+    }
+
+    #[test]
+    fn other_weapons_cannot_have_one() {
+        let game_data = GameData::default();
+        let mut stats = game_data.weapon(WeaponKind::Halo).clone();
+        stats.projectile = game_data.weapon(WeaponKind::Knife).projectile;
+
+        assert_eq!(stats.validate(), Err("Aura { radius: 80.0 } weapons don't fire projectiles".to_string()));
+    }
+}
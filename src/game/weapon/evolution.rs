@@ -0,0 +1,63 @@
+use serde::Deserialize;
+
+use crate::game::passive::component::PassiveKind;
+use crate::game::weapon::component::{Weapon, WeaponKind, WeaponStats};
+
+// A recipe from `assets/data/game.weapons.ron`: once the weapon is at its max rank and its wielder owns
+// `passive`, the next boss chest they open offers to turn it into `into`.
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct Evolution {
+    pub passive: PassiveKind,
+    pub into: WeaponKind,
+}
+
+// What `weapon` can evolve into right now, given the passives its wielder owns.
+pub fn available_evolution(weapon: &Weapon, stats: &WeaponStats, passives: &[PassiveKind]) -> Option<WeaponKind> {
+    let evolution = stats.evolution?;
+
+    (weapon.rank >= stats.max_rank && passives.contains(&evolution.passive)).then_some(evolution.into)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::resource::GameData;
+
+    fn axe(rank: u32) -> (Weapon, WeaponStats) {
+        let stats = GameData::default().weapon(WeaponKind::Axe).clone();
+        let mut weapon = Weapon::new(WeaponKind::Axe, &stats);
+        weapon.rank = rank;
+
+        (weapon, stats)
+    }
+
+    #[test]
+    fn maxed_weapons_evolve_with_their_passive() {
+        let (weapon, stats) = axe(GameData::default().weapon(WeaponKind::Axe).max_rank);
+
+        assert_eq!(available_evolution(&weapon, &stats, &[PassiveKind::Might, PassiveKind::Regeneration]), Some(WeaponKind::Cleaver));
+    }
+
+    #[test]
+    fn weapons_below_max_rank_do_not_evolve() {
+        let (weapon, stats) = axe(1);
+
+        assert_eq!(available_evolution(&weapon, &stats, &[PassiveKind::Regeneration]), None);
+    }
+
+    #[test]
+    fn weapons_do_not_evolve_without_their_passive() {
+        let (weapon, stats) = axe(GameData::default().weapon(WeaponKind::Axe).max_rank);
+
+        assert_eq!(available_evolution(&weapon, &stats, &[]), None);
+        assert_eq!(available_evolution(&weapon, &stats, &[PassiveKind::Might]), None);
+    }
+
+    #[test]
+    fn evolved_weapons_have_no_recipe() {
+        let stats = GameData::default().weapon(WeaponKind::Cleaver).clone();
+        let weapon = Weapon::new(WeaponKind::Cleaver, &stats);
+
+        assert_eq!(available_evolution(&weapon, &stats, &[PassiveKind::Regeneration]), None);
+    }
+}
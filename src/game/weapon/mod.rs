@@ -0,0 +1,3 @@
+pub mod component;
+pub mod evolution;
+pub mod system;
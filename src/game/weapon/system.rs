@@ -0,0 +1,339 @@
+use std::f32::consts::TAU;
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy::sprite::collide_aabb::collide;
+
+use crate::data::resource::GameData;
+use crate::game::GameEntity;
+use crate::game::enemy::component::Enemy;
+use crate::game::health::component::Health;
+use crate::game::health::event::DamageEvent;
+use crate::game::movement::component::Collidable;
+use crate::game::movement::event::KnockbackEvent;
+use crate::game::pool::resource::EntityPool;
+use crate::game::prop::component::Prop;
+use crate::game::sprite::component::FacingDirection;
+use crate::game::stats::component::{Stat, Stats};
+use crate::game::status::event::ApplyStatusEvent;
+use crate::game::targeting::Targeting;
+use crate::game::weapon::component::{
+    Bouncing, DamageZone, Homing, Orbiting, Piercing, Projectile, Weapon, WeaponBehavior,
+};
+
+const PROJECTILE_HIT_STUN_SECS: f32 = 0.15;
+// How long a whip's lash lingers.
+const WHIP_SECS: f32 = 0.2;
+// Auras and hazards hit everything inside them this often.
+const DAMAGE_ZONE_INTERVAL_SECS: f32 = 0.5;
+// Just above the level floor, under everything standing on it.
+const DAMAGE_ZONE_Z: f32 = 0.01;
+// How far from the wielder hazards look for a crowd of enemies to land on.
+const HAZARD_THROW_RANGE: f32 = 300.;
+
+// Fires every weapon whose cooldown has elapsed the way its `WeaponBehavior` says to. Weapons that aim go for
+// the closest enemy, or straight ahead in the wielder's facing direction when there are no enemies; hazards are
+// thrown into the thick of the nearby enemies instead.
+pub fn weapon_fire_system(
+    mut commands: Commands,
+    fixed_time: Res<FixedTime>,
+    game_data: Res<GameData>,
+    mut weapons: Query<(&mut Weapon, &Parent)>,
+    wielders: Query<(&Transform, &FacingDirection, Option<&Stats>)>,
+    targeting: Targeting,
+    mut projectile_pool: ResMut<EntityPool<Projectile>>,
+) {
+    for (mut weapon, parent) in &mut weapons {
+        if !weapon.cooldown.tick(fixed_time.period).just_finished() {
+            continue;
+        }
+
+        let wielder = parent.get();
+        let Ok((wielder_transform, facing_direction, stats)) = wielders.get(wielder) else { continue };
+        let origin = wielder_transform.translation.truncate();
+        let translation = origin.extend(wielder_transform.translation.z);
+
+        let facing = match facing_direction {
+            FacingDirection::East => Vec2::X,
+            FacingDirection::West => Vec2::NEG_X,
+        };
+        let nearest_enemy = targeting.nearest(origin).map(|target| target.position);
+        let direction = nearest_enemy.map_or(facing, |target| (target - origin).normalize_or_zero());
+
+        let weapon_stats = game_data.weapon(weapon.kind);
+        let damage = weapon_stats.damage_at(weapon.rank) * stats.map_or(1., |stats| stats.get(Stat::Damage));
+        let status_effect = weapon_stats.status_effect.map(|status| status.effect());
+        let projectile = |velocity: Vec2, size: Vec2, lifetime_secs: f32| Projectile {
+            damage,
+            velocity,
+            size,
+            lifetime: Timer::from_seconds(lifetime_secs, TimerMode::Once),
+            knockback: weapon_stats.knockback,
+            status_effect,
+        };
+        let damage_zone = |radius: f32, lifetime_secs: f32, follow: Option<Entity>| DamageZone {
+            radius,
+            damage,
+            knockback: weapon_stats.knockback,
+            status_effect,
+            interval: Timer::from_seconds(DAMAGE_ZONE_INTERVAL_SECS, TimerMode::Repeating),
+            lifetime: Timer::from_seconds(lifetime_secs, TimerMode::Once),
+            follow,
+        };
+        let flying = weapon_stats.projectile.map(|stats| projectile(direction * stats.speed, stats.size, stats.lifetime_secs));
+
+        match (weapon_stats.behavior, flying) {
+            (WeaponBehavior::Projectile, Some(flying)) => {
+                spawn_projectile_at(&mut commands, &mut projectile_pool, translation, flying, ());
+            }
+            (WeaponBehavior::Homing { turn_rate }, Some(flying)) => {
+                spawn_projectile_at(&mut commands, &mut projectile_pool, translation, flying, Homing { turn_rate });
+            }
+            (WeaponBehavior::Bouncing { bounces }, Some(flying)) => {
+                spawn_projectile_at(&mut commands, &mut projectile_pool, translation, flying, Bouncing { remaining: bounces, last_hit: None });
+            }
+            // `WeaponStats::validate` makes sure these always have a projectile.
+            (WeaponBehavior::Projectile | WeaponBehavior::Homing { .. } | WeaponBehavior::Bouncing { .. }, None) => {}
+            (WeaponBehavior::Orbit { blades, radius, angular_speed, size }, _) => {
+                // Blades last until the next lot replaces them.
+                for blade in 0..blades {
+                    let orbiting = Orbiting { wielder, angle: blade as f32 * TAU / blades as f32, radius, angular_speed };
+                    let blade = projectile(Vec2::ZERO, size, weapon_stats.cooldown_secs);
+                    spawn_projectile_at(&mut commands, &mut projectile_pool, translation, blade, (orbiting, Piercing::default()));
+                }
+            }
+            (WeaponBehavior::Aura { radius }, _) => {
+                let zone = damage_zone(radius, weapon_stats.cooldown_secs, Some(wielder));
+                spawn_damage_zone_at(&mut commands, origin.extend(DAMAGE_ZONE_Z), zone, Color::rgba(1., 0.95, 0.6, 0.2));
+            }
+            (WeaponBehavior::Whip { range, width }, _) => {
+                let lash = projectile(Vec2::ZERO, Vec2::new(range, width), WHIP_SECS);
+                spawn_projectile_at(&mut commands, &mut projectile_pool, translation + (facing * range / 2.).extend(0.), lash, Piercing::default());
+            }
+            (WeaponBehavior::Hazard { radius, duration_secs }, _) => {
+                let center = targeting.densest_cluster(origin, HAZARD_THROW_RANGE, radius)
+                    .unwrap_or(origin + facing * radius);
+                let zone = damage_zone(radius, duration_secs, None);
+                spawn_damage_zone_at(&mut commands, center.extend(DAMAGE_ZONE_Z), zone, Color::rgba(1., 0.4, 0.1, 0.35));
+            }
+        }
+    }
+}
+
+pub fn spawn_projectile_at(
+    commands: &mut Commands,
+    projectile_pool: &mut EntityPool<Projectile>,
+    translation: Vec3,
+    projectile: Projectile,
+    behavior: impl Bundle,
+) {
+    projectile_pool.spawn(
+        commands,
+        (
+            SpriteBundle {
+                sprite: Sprite {
+                    color: Color::rgb(1., 1., 1.),
+                    custom_size: Some(projectile.size),
+                    ..default()
+                },
+                transform: Transform::from_translation(translation)
+                    .with_rotation(Quat::from_rotation_z(projectile.velocity.y.atan2(projectile.velocity.x))),
+                ..default()
+            },
+            GameEntity,
+            projectile,
+            behavior,
+        )
+    );
+}
+
+pub fn spawn_damage_zone_at(commands: &mut Commands, translation: Vec3, zone: DamageZone, color: Color) {
+    commands.spawn((
+        SpriteBundle {
+            sprite: Sprite {
+                color,
+                custom_size: Some(Vec2::splat(zone.radius * 2.)),
+                ..default()
+            },
+            transform: Transform::from_translation(translation),
+            ..default()
+        },
+        GameEntity,
+        zone,
+    ));
+}
+
+pub fn homing_system(
+    fixed_time: Res<FixedTime>,
+    mut projectiles: Query<(&mut Projectile, &mut Transform, &Homing)>,
+    targeting: Targeting,
+) {
+    let delta_secs = fixed_time.period.as_secs_f32();
+
+    for (mut projectile, mut transform, homing) in &mut projectiles {
+        let position = transform.translation.truncate();
+        let Some(target) = targeting.nearest(position) else { continue };
+
+        let angle = projectile.velocity.angle_between(target.position - position);
+        if angle.is_nan() {
+            continue;
+        }
+
+        let max_turn = homing.turn_rate * delta_secs;
+        projectile.velocity = Vec2::from_angle(angle.clamp(-max_turn, max_turn)).rotate(projectile.velocity);
+        transform.rotation = Quat::from_rotation_z(projectile.velocity.y.atan2(projectile.velocity.x));
+    }
+}
+
+pub fn orbit_system(
+    fixed_time: Res<FixedTime>,
+    mut blades: Query<(&mut Orbiting, &mut Transform)>,
+    wielders: Query<&Transform, Without<Orbiting>>,
+) {
+    let delta_secs = fixed_time.period.as_secs_f32();
+
+    for (mut orbiting, mut transform) in &mut blades {
+        let Ok(wielder_transform) = wielders.get(orbiting.wielder) else { continue };
+
+        orbiting.angle = (orbiting.angle + orbiting.angular_speed * delta_secs) % TAU;
+        let offset = Vec2::from_angle(orbiting.angle) * orbiting.radius;
+        transform.translation = wielder_transform.translation + offset.extend(0.);
+        transform.rotation = Quat::from_rotation_z(orbiting.angle);
+    }
+}
+
+// Moves projectiles and damages what they touch. Most are used up by the first enemy or prop they hit, but
+// piercing ones carry on through everything and bouncing ones fly on to the next nearest target.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn projectile_system(
+    mut commands: Commands,
+    fixed_time: Res<FixedTime>,
+    mut projectiles: Query<(Entity, &mut Projectile, &mut Transform, Option<&mut Piercing>, Option<&mut Bouncing>)>,
+    targets: Query<(Entity, &Transform, &Collidable), (Or<(With<Enemy>, With<Prop>)>, Without<Projectile>)>,
+    mut damage_events: EventWriter<DamageEvent>,
+    mut status_events: EventWriter<ApplyStatusEvent>,
+    mut knockback_events: EventWriter<KnockbackEvent>,
+    mut projectile_pool: ResMut<EntityPool<Projectile>>,
+) {
+    let _span = info_span!("projectile collision").entered();
+
+    for (entity, mut projectile, mut transform, mut piercing, mut bouncing) in &mut projectiles {
+        if projectile.lifetime.tick(fixed_time.period).finished() {
+            projectile_pool.release(&mut commands, entity);
+            continue;
+        }
+
+        transform.translation += (projectile.velocity * fixed_time.period.as_secs_f32()).extend(0.);
+
+        let mut projectile_translation = transform.translation;
+        projectile_translation.z = 1.0;
+
+        for (target, target_transform, target_collidable) in &targets {
+            let already_hit = piercing.as_ref().is_some_and(|piercing| piercing.hit.contains(&target))
+                || bouncing.as_ref().is_some_and(|bouncing| bouncing.last_hit == Some(target));
+            if already_hit {
+                continue;
+            }
+
+            let mut target_translation = target_transform.translation;
+            target_translation.z = 1.0;
+
+            if collide(projectile_translation, projectile.size, target_translation, target_collidable.size()).is_none() {
+                continue;
+            }
+
+            damage_events.send(DamageEvent { target, amount: projectile.damage });
+            // Pushing away from a point just behind the projectile knocks enemies along its flight path.
+            knockback_events.send(KnockbackEvent {
+                target,
+                source: transform.translation.truncate() - projectile.velocity.normalize_or_zero(),
+                force: projectile.knockback,
+                stun_secs: PROJECTILE_HIT_STUN_SECS,
+            });
+            if let Some(effect) = projectile.status_effect {
+                status_events.send(ApplyStatusEvent { target, effect });
+            }
+
+            if let Some(piercing) = piercing.as_mut() {
+                piercing.hit.push(target);
+                continue;
+            }
+
+            let position = projectile_translation.truncate();
+            let bounce_to = bouncing.as_ref().filter(|bouncing| bouncing.remaining > 0).and_then(|_| {
+                let others = targets.iter().filter(|(other, _, _)| *other != target);
+                nearest_to(position, others.map(|(_, other_transform, _)| other_transform.translation.truncate()))
+            });
+            if let (Some(bouncing), Some(next)) = (bouncing.as_mut(), bounce_to) {
+                bouncing.remaining -= 1;
+                bouncing.last_hit = Some(target);
+                projectile.velocity = (next - position).normalize_or_zero() * projectile.velocity.length();
+                transform.rotation = Quat::from_rotation_z(projectile.velocity.y.atan2(projectile.velocity.x));
+                break;
+            }
+
+            projectile_pool.release(&mut commands, entity);
+            break;
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn damage_zone_system(
+    mut commands: Commands,
+    fixed_time: Res<FixedTime>,
+    mut zones: Query<(Entity, &mut DamageZone, &mut Transform)>,
+    followed: Query<&Transform, Without<DamageZone>>,
+    targets: Query<(Entity, &Transform), (Or<(With<Enemy>, With<Prop>)>, With<Health>, Without<DamageZone>)>,
+    mut damage_events: EventWriter<DamageEvent>,
+    mut status_events: EventWriter<ApplyStatusEvent>,
+    mut knockback_events: EventWriter<KnockbackEvent>,
+) {
+    let _span = info_span!("damage zones").entered();
+
+    for (entity, mut zone, mut transform) in &mut zones {
+        if zone.lifetime.tick(fixed_time.period).finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        if let Some(follow) = zone.follow {
+            let Ok(followed_transform) = followed.get(follow) else {
+                commands.entity(entity).despawn();
+                continue;
+            };
+            transform.translation = followed_transform.translation.truncate().extend(transform.translation.z);
+        }
+
+        if !zone.interval.tick(fixed_time.period).just_finished() {
+            continue;
+        }
+
+        let center = transform.translation.truncate();
+        for (target, target_transform) in &targets {
+            if target_transform.translation.truncate().distance(center) > zone.radius {
+                continue;
+            }
+
+            damage_events.send(DamageEvent { target, amount: zone.damage });
+            knockback_events.send(KnockbackEvent { target, source: center, force: zone.knockback, stun_secs: PROJECTILE_HIT_STUN_SECS });
+            if let Some(effect) = zone.status_effect {
+                status_events.send(ApplyStatusEvent { target, effect });
+            }
+        }
+    }
+}
+
+fn nearest_to(origin: Vec2, positions: impl Iterator<Item = Vec2>) -> Option<Vec2> {
+    positions.min_by(|a, b| a.distance_squared(origin).total_cmp(&b.distance_squared(origin)))
+}
+
+// Picks up cooldown changes from `assets/data/game.weapons.ron`; everything else is read from `GameData` on every shot.
+pub fn weapon_stats_reload_system(game_data: Res<GameData>, mut weapons: Query<&mut Weapon>) {
+    for mut weapon in &mut weapons {
+        let cooldown = Duration::from_secs_f32(game_data.weapon(weapon.kind).cooldown_secs);
+        if weapon.cooldown.duration() != cooldown {
+            weapon.cooldown.set_duration(cooldown);
+        }
+    }
+}
@@ -0,0 +1,156 @@
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use bevy::audio::AudioSource;
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+
+use crate::{AppState, EndlessStreamPlugin};
+use crate::data::resource::GameData;
+use crate::game::character::SelectedCharacter;
+use crate::game::health::component::Health;
+use crate::game::player::component::PlayerControlled;
+use crate::game::simulation::SIMULATION_TICK_SECS;
+use crate::game::simulation::resource::{RunSeed, SimulationTick};
+use crate::menu::MenuState;
+use crate::replay::resource::ReplayPath;
+use crate::save::resource::SavePath;
+
+// How many frames `advance_ticks` will wait for a single tick before giving up.
+const MAX_FRAMES_PER_TICK: u64 = 10;
+const LOADING_TIMEOUT: Duration = Duration::from_secs(10);
+
+// Builds the game without a window, GPU or audio device. Every `App::update` advances time by exactly one
+// simulation tick, so the game runs as fast as the CPU allows and plays out the same on every machine.
+// Save and replay files go to `data_dir`, keeping headless runs away from the player's own.
+pub fn headless_app(data_dir: &Path) -> App {
+    let mut app = App::new();
+
+    app.add_plugins(MinimalPlugins)
+        .add_plugins((AssetPlugin::default(), TransformPlugin, HierarchyPlugin, InputPlugin))
+        // Only the data files get loaded without the rendering and audio plugins' loaders, but the other handles still need somewhere to live.
+        .add_asset::<Image>()
+        .add_asset::<TextureAtlas>()
+        .add_asset::<Font>()
+        .add_asset::<AudioSource>()
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(SIMULATION_TICK_SECS)))
+        .insert_resource(SavePath(data_dir.join("progress.ron")))
+        .insert_resource(ReplayPath(data_dir.join("last.ron")))
+        .add_plugins(EndlessStreamPlugin);
+
+    app
+}
+
+// Drives a headless game from tests: start a run, hold keys down, advance the simulation and inspect the world.
+pub struct HeadlessHarness {
+    pub app: App,
+    data_dir: PathBuf,
+}
+
+impl HeadlessHarness {
+    pub fn new() -> Self {
+        static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+        let data_dir = std::env::temp_dir().join(format!(
+            "endless_stream_headless_{}_{}",
+            std::process::id(),
+            NEXT_ID.fetch_add(1, Ordering::Relaxed),
+        ));
+        let _ = std::fs::remove_dir_all(&data_dir);
+
+        let mut harness = HeadlessHarness { app: headless_app(&data_dir), data_dir };
+        // Skips the splash screen and waits for loading to finish; only the data files can actually load headless, so this is quick.
+        harness.app.world.insert_resource(NextState(Some(AppState::Loading)));
+        let started = Instant::now();
+        while harness.state() != AppState::MainMenu {
+            assert!(started.elapsed() < LOADING_TIMEOUT, "headless app never finished loading");
+            harness.app.update();
+        }
+
+        harness
+    }
+
+    // Starts a run as if the character had been picked in the menu, returning once the first tick has run.
+    pub fn start_run(&mut self, character_id: &str, seed: u64) -> &mut Self {
+        assert!(self.app.world.resource::<GameData>().character(character_id).is_some(), "unknown character {}", character_id);
+
+        self.app.world.resource_mut::<SelectedCharacter>().0 = character_id.to_string();
+        self.app.world.insert_resource(RunSeed(seed));
+        self.app.world.resource_mut::<NextState<AppState>>().set(AppState::GamePlaying);
+        self.app.world.resource_mut::<NextState<MenuState>>().set(MenuState::Disabled);
+
+        self.advance_ticks(1)
+    }
+
+    pub fn press(&mut self, key: KeyCode) -> &mut Self {
+        self.app.world.resource_mut::<Input<KeyCode>>().press(key);
+        self
+    }
+
+    pub fn release(&mut self, key: KeyCode) -> &mut Self {
+        self.app.world.resource_mut::<Input<KeyCode>>().release(key);
+        self
+    }
+
+    // Runs frames until `ticks` more simulation ticks have happened.
+    pub fn advance_ticks(&mut self, ticks: u64) -> &mut Self {
+        let target = self.tick() + ticks;
+
+        for _ in 0..ticks * MAX_FRAMES_PER_TICK {
+            if self.tick() >= target {
+                return self;
+            }
+
+            self.app.update();
+        }
+
+        panic!("simulation stuck at tick {} (waiting for {}); is a run in progress?", self.tick(), target);
+    }
+
+    pub fn tick(&self) -> u64 {
+        self.app.world.resource::<SimulationTick>().0
+    }
+
+    pub fn state(&self) -> AppState {
+        *self.app.world.resource::<State<AppState>>().get()
+    }
+
+    pub fn player(&mut self) -> Entity {
+        self.app.world.query_filtered::<Entity, With<PlayerControlled>>().single(&self.app.world)
+    }
+
+    pub fn player_translation(&mut self) -> Vec3 {
+        let player = self.player();
+        self.app.world.get::<Transform>(player).expect("player has no Transform").translation
+    }
+
+    pub fn player_health(&mut self) -> f32 {
+        let player = self.player();
+        self.app.world.get::<Health>(player).expect("player has no Health").current
+    }
+
+    // The translation of every entity with `T`, in a stable order.
+    pub fn translations<T: Component>(&mut self) -> Vec<(Entity, Vec3)> {
+        let mut translations: Vec<_> = self.app.world.query_filtered::<(Entity, &Transform), With<T>>()
+            .iter(&self.app.world)
+            .map(|(entity, transform)| (entity, transform.translation))
+            .collect();
+        translations.sort_by_key(|(entity, _)| *entity);
+
+        translations
+    }
+}
+
+impl Default for HeadlessHarness {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for HeadlessHarness {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.data_dir);
+    }
+}
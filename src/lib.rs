@@ -0,0 +1,55 @@
+use bevy::prelude::*;
+use character_select::CharacterSelectPlugin;
+use data::DataPlugin;
+use menu::MenuPlugin;
+use game::GamePlugin;
+use loading::LoadingPlugin;
+use replay::ReplayPlugin;
+use save::SavePlugin;
+use settings::resource::Settings;
+use shop::ShopPlugin;
+use splash::SplashPlugin;
+
+pub mod audio;
+pub mod benchmark;
+pub mod character_select;
+pub mod cli;
+pub mod data;
+pub mod debug;
+pub mod harness;
+pub mod loading;
+pub mod menu;
+pub mod game;
+pub mod replay;
+pub mod save;
+pub mod settings;
+pub mod shop;
+pub mod splash;
+
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash, States)]
+pub enum AppState {
+    MainMenu,
+    #[default]
+    SplashScreen,
+    Loading,
+    GamePlaying,
+}
+
+// The whole game minus the window, rendering and audio, so it can run headless as well.
+pub struct EndlessStreamPlugin;
+
+impl Plugin for EndlessStreamPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_state::<AppState>()
+            .init_resource::<Settings>()
+            .add_plugins(DataPlugin)
+            .add_plugins(SplashPlugin)
+            .add_plugins(LoadingPlugin)
+            .add_plugins(MenuPlugin)
+            .add_plugins(GamePlugin)
+            .add_plugins(SavePlugin)
+            .add_plugins(ShopPlugin)
+            .add_plugins(CharacterSelectPlugin)
+            .add_plugins(ReplayPlugin);
+    }
+}
@@ -0,0 +1,7 @@
+use bevy::prelude::*;
+
+#[derive(Component)]
+pub struct LoadingProgressBar;
+
+#[derive(Component)]
+pub struct LoadingProgressText;
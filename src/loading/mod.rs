@@ -0,0 +1,28 @@
+use bevy::prelude::*;
+
+use crate::AppState;
+use crate::data::DataReloadSet;
+use crate::data::resource::GameData;
+use crate::game::enemy::system::enemy_archetype_reload_system;
+use resource::{GameAssets, LoadingTarget};
+use system::*;
+
+pub mod component;
+pub mod resource;
+pub mod system;
+
+pub struct LoadingPlugin;
+
+impl Plugin for LoadingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LoadingTarget>()
+            .add_systems(OnEnter(AppState::Loading), (start_loading, setup_loading_screen))
+            .add_systems(Update, loading_progress_system.run_if(in_state(AppState::Loading)))
+            .add_systems(OnExit(AppState::Loading), cleanup_loading_screen)
+            // Living enemies are pointed at their archetype's atlas when it reloads, so it has to exist by then.
+            .add_systems(Update, atlas_reload_system
+                .after(DataReloadSet)
+                .before(enemy_archetype_reload_system)
+                .run_if(resource_exists::<GameAssets>().and_then(resource_changed::<GameData>())));
+    }
+}
@@ -0,0 +1,44 @@
+use bevy::asset::HandleId;
+use bevy::audio::AudioSource;
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+
+use crate::AppState;
+use crate::audio::event::{MusicTrack, Sfx};
+use crate::menu::MenuState;
+
+// Every asset the game uses, loaded up front during `AppState::Loading` so nothing pops in mid-run.
+#[derive(Resource)]
+pub struct GameAssets {
+    pub tree: Handle<Image>,
+    pub font: Handle<Font>,
+    // Sprite sheet atlases, keyed by character and enemy archetype id.
+    pub character_atlases: HashMap<String, Handle<TextureAtlas>>,
+    pub enemy_atlases: HashMap<String, Handle<TextureAtlas>>,
+    pub music: HashMap<MusicTrack, Handle<AudioSource>>,
+    pub sfx: HashMap<Sfx, Handle<AudioSource>>,
+    // Everything above that has to be read from disk, for tracking loading progress.
+    pub(crate) files: Vec<HandleId>,
+}
+
+// Where to go once everything has loaded; the main menu unless the game was launched straight into a run.
+#[derive(Resource)]
+pub struct LoadingTarget {
+    pub app_state: AppState,
+    pub menu_state: MenuState,
+}
+
+impl Default for LoadingTarget {
+    fn default() -> Self {
+        LoadingTarget {
+            app_state: AppState::MainMenu,
+            menu_state: MenuState::MainMenu,
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct LoadingScreen {
+    pub root: Entity,
+    pub camera: Entity,
+}
@@ -0,0 +1,201 @@
+use bevy::asset::LoadState;
+use bevy::prelude::*;
+
+use crate::AppState;
+use crate::audio::event::{MusicTrack, Sfx};
+use crate::data::resource::{DataFiles, GameData};
+use crate::loading::component::{LoadingProgressBar, LoadingProgressText};
+use crate::loading::resource::{GameAssets, LoadingScreen, LoadingTarget};
+use crate::menu::MenuState;
+
+pub fn start_loading(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    data_files: Res<DataFiles>,
+    game_data: Res<GameData>,
+    mut texture_atlases: ResMut<Assets<TextureAtlas>>,
+) {
+    // The data files are already loading; waiting for them means the run starts with what's on disk.
+    let mut files = data_files.ids().to_vec();
+    let mut load = |path: &str| {
+        let handle: HandleUntyped = asset_server.load_untyped(path);
+        // Several characters and archetypes share a sprite sheet.
+        if !files.contains(&handle.id()) {
+            files.push(handle.id());
+        }
+
+        handle
+    };
+
+    let tree = load("environment/tree-sprite.png").typed();
+    let font = load("font/Golden Age.ttf").typed();
+
+    let mut atlas = |path: &str, size: Vec2, columns: usize| {
+        texture_atlases.add(TextureAtlas::from_grid(load(path).typed(), size, columns, 1, None, None))
+    };
+
+    let character_atlases = game_data.characters.iter()
+        .map(|character| (character.id.clone(), atlas(&character.sprite_sheet, character.sprite_size, character.sprite_columns)))
+        .collect();
+    let enemy_atlases = game_data.enemies.iter()
+        .map(|archetype| (archetype.id.clone(), atlas(&archetype.sprite_sheet, archetype.sprite_size, archetype.sprite_columns)))
+        .collect();
+
+    let music = MusicTrack::ALL.into_iter().map(|track| (track, load(track.path()).typed())).collect();
+    let sfx = Sfx::ALL.into_iter().map(|sfx| (sfx, load(sfx.path()).typed())).collect();
+
+    commands.insert_resource(GameAssets {
+        tree,
+        font,
+        character_atlases,
+        enemy_atlases,
+        music,
+        sfx,
+        files,
+    });
+}
+
+// Characters and enemy archetypes can be added or get a new sprite sheet while the game runs, so their atlases
+// are rebuilt to match. Edits that leave every sprite sheet as it was, like tuning an enemy's speed, keep them.
+pub fn atlas_reload_system(
+    asset_server: Res<AssetServer>,
+    game_data: Res<GameData>,
+    mut game_assets: ResMut<GameAssets>,
+    mut texture_atlases: ResMut<Assets<TextureAtlas>>,
+    mut last_sheets: Local<Vec<SpriteSheet>>,
+) {
+    let sheets = sprite_sheets(&game_data);
+    if sheets == *last_sheets {
+        return;
+    }
+    *last_sheets = sheets;
+
+    let mut atlas = |path: &str, size: Vec2, columns: usize| {
+        texture_atlases.add(TextureAtlas::from_grid(asset_server.load(path), size, columns, 1, None, None))
+    };
+
+    game_assets.character_atlases = game_data.characters.iter()
+        .map(|character| (character.id.clone(), atlas(&character.sprite_sheet, character.sprite_size, character.sprite_columns)))
+        .collect();
+    game_assets.enemy_atlases = game_data.enemies.iter()
+        .map(|archetype| (archetype.id.clone(), atlas(&archetype.sprite_sheet, archetype.sprite_size, archetype.sprite_columns)))
+        .collect();
+}
+
+// Who uses a sprite sheet, and how it's cut into frames.
+#[derive(PartialEq)]
+pub struct SpriteSheet {
+    id: String,
+    path: String,
+    size: Vec2,
+    columns: usize,
+}
+
+fn sprite_sheets(game_data: &GameData) -> Vec<SpriteSheet> {
+    let characters = game_data.characters.iter()
+        .map(|character| (&character.id, &character.sprite_sheet, character.sprite_size, character.sprite_columns));
+    let enemies = game_data.enemies.iter()
+        .map(|archetype| (&archetype.id, &archetype.sprite_sheet, archetype.sprite_size, archetype.sprite_columns));
+
+    characters.chain(enemies)
+        .map(|(id, path, size, columns)| SpriteSheet { id: id.clone(), path: path.clone(), size, columns })
+        .collect()
+}
+
+pub fn setup_loading_screen(mut commands: Commands) {
+    let camera = commands.spawn(Camera2dBundle::default()).id();
+    let root = commands
+        .spawn(NodeBundle {
+            style: Style {
+                width: Val::Percent(100.),
+                height: Val::Percent(100.),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            ..default()
+        }).with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    "Loading... 0%",
+                    TextStyle {
+                        font_size: 40.,
+                        color: Color::WHITE,
+                        ..default()
+                    },
+                ),
+                LoadingProgressText,
+            ));
+
+            // The bar's background, with the bar itself growing inside it.
+            parent.spawn(NodeBundle {
+                style: Style {
+                    width: Val::Percent(40.),
+                    height: Val::Px(20.),
+                    margin: UiRect::all(Val::Px(10.)),
+                    ..default()
+                },
+                background_color: Color::rgb(0.2, 0.2, 0.2).into(),
+                ..default()
+            }).with_children(|parent| {
+                parent.spawn((
+                    NodeBundle {
+                        style: Style {
+                            width: Val::Percent(0.),
+                            height: Val::Percent(100.),
+                            ..default()
+                        },
+                        background_color: Color::rgb(1., 0., 0.).into(),
+                        ..default()
+                    },
+                    LoadingProgressBar,
+                ));
+            });
+        })
+        .id();
+
+    commands.insert_resource(LoadingScreen { root, camera });
+}
+
+// Moves on once every file has either loaded or failed to; a missing file is logged but doesn't stop the game.
+pub fn loading_progress_system(
+    asset_server: Res<AssetServer>,
+    game_assets: Res<GameAssets>,
+    target: Res<LoadingTarget>,
+    mut bars: Query<&mut Style, With<LoadingProgressBar>>,
+    mut texts: Query<&mut Text, With<LoadingProgressText>>,
+    mut game_state: ResMut<NextState<AppState>>,
+    mut menu_state: ResMut<NextState<MenuState>>,
+) {
+    let load_states: Vec<_> = game_assets.files.iter().map(|id| asset_server.get_load_state(*id)).collect();
+    let done = load_states.iter().filter(|state| matches!(state, LoadState::Loaded | LoadState::Failed)).count();
+    let progress = if load_states.is_empty() { 1. } else { done as f32 / load_states.len() as f32 };
+
+    for mut style in &mut bars {
+        style.width = Val::Percent(progress * 100.);
+    }
+
+    for mut text in &mut texts {
+        text.sections[0].value = format!("Loading... {:.0}%", progress * 100.);
+    }
+
+    if done < load_states.len() {
+        return;
+    }
+
+    for (id, state) in game_assets.files.iter().zip(&load_states) {
+        if let (LoadState::Failed, Some(path)) = (state, asset_server.get_handle_path(*id)) {
+            warn!("Failed to load {}", path.path().display());
+        }
+    }
+
+    game_state.set(target.app_state);
+    menu_state.set(target.menu_state);
+}
+
+pub fn cleanup_loading_screen(mut commands: Commands, loading_screen: Res<LoadingScreen>) {
+    commands.entity(loading_screen.root).despawn_recursive();
+    commands.entity(loading_screen.camera).despawn_recursive();
+    commands.remove_resource::<LoadingScreen>();
+}
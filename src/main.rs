@@ -1,24 +1,52 @@
+use std::time::Duration;
+
+use bevy::asset::ChangeWatcher;
+use bevy::log::LogPlugin;
 use bevy::prelude::*;
-use menu::MenuPlugin;
-use game::GamePlugin;
+use clap::Parser;
+use endless_stream::EndlessStreamPlugin;
+use endless_stream::audio::GameAudioPlugin;
+use endless_stream::benchmark::run_benchmark;
+use endless_stream::cli::LaunchArgs;
+use endless_stream::debug::DebugPlugin;
+use endless_stream::harness::headless_app;
 
-pub mod menu;
-pub mod game;
+fn main() {
+    let args = LaunchArgs::parse();
 
-#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash, States)]
-pub enum AppState {
-    #[default]
-    MainMenu,
-    SplashScreen,
-    Loading,
-    GamePlaying,
-}
+    if let Some(config) = args.benchmark_config() {
+        let report = run_benchmark(config);
+        println!("{}", serde_json::to_string_pretty(&report).expect("benchmark report is always serializable"));
+        return;
+    }
 
-fn main() {
-    App::new()
-        .add_state::<AppState>()
-        .add_plugins(DefaultPlugins)
-        .add_plugins(MenuPlugin)
-        .add_plugins(GamePlugin)
-        .run();
+    let mut app = if args.headless {
+        // Headless runs keep their saves and replays out of the player's way.
+        let mut app = headless_app(&std::env::temp_dir().join("endless_stream_headless"));
+        app.add_plugins(LogPlugin {
+            // There are no image, font or audio loaders without rendering and audio, so every one of those would warn about it.
+            filter: "wgpu=error,bevy_asset=error".to_string(),
+            ..default()
+        });
+        app
+    } else {
+        let mut app = App::new();
+        app.add_plugins(DefaultPlugins
+            .set(WindowPlugin {
+                primary_window: Some(args.window()),
+                ..default()
+            })
+            // Lets levels, enemies and balance numbers in `assets/data` be tuned while the game runs.
+            .set(AssetPlugin {
+                watch_for_changes: ChangeWatcher::with_delay(Duration::from_millis(200)),
+                ..default()
+            }))
+            .add_plugins(EndlessStreamPlugin)
+            .add_plugins(GameAudioPlugin)
+            .add_plugins(DebugPlugin);
+        app
+    };
+
+    args.configure(&mut app);
+    app.run();
 }
@@ -3,5 +3,8 @@ use bevy::prelude::*;
 #[derive(Component)]
 pub enum MenuButtonAction {
     Play,
+    Shop,
+    WatchReplay,
+    BackToMainMenu,
     Quit
-}
\ No newline at end of file
+}
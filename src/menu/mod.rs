@@ -14,58 +14,77 @@ const HOVERED_BUTTON_COLOR: Color = Color::rgb(0., 0., 1.);
 
 #[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash, States)]
 pub enum MenuState {
-    #[default]
     MainMenu,
     PauseMenu,
+    Shop,
+    CharacterSelect,
+    // No menu screen is shown, e.g. while loading or while a run is being played.
+    #[default]
+    Disabled,
 }
 
 pub struct MenuPlugin;
 
 impl Plugin for MenuPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(AppState::MainMenu), (setup_menu, setup_2d_camera))
+        app.add_state::<MenuState>()
+            .add_systems(OnEnter(AppState::MainMenu), setup_2d_camera)
+            .add_systems(OnEnter(MenuState::MainMenu), setup_menu)
             .add_systems(Update, menu_button_feel_system.run_if(in_state(AppState::MainMenu)))
             .add_systems(Update,menu_action_system.run_if(in_state(AppState::MainMenu)))
-            .add_systems(OnExit(AppState::MainMenu), cleanup_menu);
+            .add_systems(OnExit(MenuState::MainMenu), cleanup_menu)
+            .add_systems(OnExit(AppState::MainMenu), cleanup_menu_camera);
     }
 }
 
 pub fn setup_menu(mut commands: Commands) {
-    let hover_me_button = commands
+    let root = commands
         .spawn(NodeBundle {
             // Centered column for buttons to go into.
             style: Style {
                 width: Val::Percent(100.),
+                height: Val::Percent(100.),
+                flex_direction: FlexDirection::Column,
                 justify_content: JustifyContent::Center,
                 align_items: AlignItems::Center,
                 ..default()
             },
             ..default()
         }).with_children(|parent| {
-        parent.spawn((ButtonBundle {
-            style: Style {
-                width: Val::Percent(40.),
-                height: Val::Px(100.),
-                justify_content: JustifyContent::Center,
-                align_items: AlignItems::Center,
+            spawn_menu_button(parent, "Play", MenuButtonAction::Play);
+            spawn_menu_button(parent, "Shop", MenuButtonAction::Shop);
+            spawn_menu_button(parent, "Watch replay", MenuButtonAction::WatchReplay);
+            spawn_menu_button(parent, "Quit", MenuButtonAction::Quit);
+        })
+        .id();
+
+    commands.insert_resource(MenuButtons { root })
+}
+
+// Spawns a standard menu button; `action` is whatever component the screen's action system looks for.
+pub fn spawn_menu_button(parent: &mut ChildBuilder, label: &str, action: impl Bundle) -> Entity {
+    parent.spawn((ButtonBundle {
+        style: Style {
+            width: Val::Percent(40.),
+            height: Val::Px(80.),
+            margin: UiRect::all(Val::Px(10.)),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        background_color: UNHOVERED_BUTTON_COLOR.into(),
+        ..default()
+    }, action)).with_children(|parent| {
+        parent.spawn(TextBundle::from_section(
+            label,
+            TextStyle {
+                font_size: 40.,
+                color: Color::rgb(1., 1., 1.),
                 ..default()
             },
-            background_color: UNHOVERED_BUTTON_COLOR.into(),
-            ..default()
-        }, MenuButtonAction::Play)).with_children(|parent| {
-            parent.spawn(TextBundle::from_section(
-                "Play",
-                TextStyle {
-                    font_size: 40.,
-                    color: Color::rgb(1., 1., 1.),
-                    ..default()
-                },
-            ));
-        });
+        ));
     })
-        .id();
-
-    commands.insert_resource(MenuButtons { hover_me_button })
+        .id()
 }
 
 fn setup_2d_camera(mut commands: Commands) {
@@ -73,7 +92,10 @@ fn setup_2d_camera(mut commands: Commands) {
     commands.insert_resource(MenuCamera { camera });
 }
 
-pub fn cleanup_menu(mut commands: Commands, menu_data: Res<MenuButtons>, camera: Res<MenuCamera>) {
-    commands.entity(menu_data.hover_me_button).despawn_recursive();
+pub fn cleanup_menu(mut commands: Commands, menu_data: Res<MenuButtons>) {
+    commands.entity(menu_data.root).despawn_recursive();
+}
+
+fn cleanup_menu_camera(mut commands: Commands, camera: Res<MenuCamera>) {
     commands.entity(camera.camera).despawn_recursive();
-}
\ No newline at end of file
+}
@@ -2,10 +2,10 @@ use bevy::prelude::*;
 
 #[derive(Resource)]
 pub struct MenuButtons {
-    pub hover_me_button: Entity,
+    pub root: Entity,
 }
 
 #[derive(Resource)]
 pub struct MenuCamera {
     pub camera: Entity,
-}
\ No newline at end of file
+}
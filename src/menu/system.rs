@@ -1,8 +1,12 @@
+use bevy::app::AppExit;
 use bevy::prelude::*;
 use super::{ UNHOVERED_BUTTON_COLOR, HOVERED_BUTTON_COLOR };
 use super::component::MenuButtonAction;
-use crate::AppState;
+use super::MenuState;
+use crate::replay::event::StartReplayEvent;
+use crate::replay::resource::ReplayPath;
 
+#[allow(clippy::type_complexity)]
 pub fn menu_button_feel_system(
     // mut next_state: ResMut<NextState<AppState>>,
     mut interaction_query: Query<
@@ -25,18 +29,32 @@ pub fn menu_button_feel_system(
     }
 }
 
+#[allow(clippy::type_complexity)]
 pub fn menu_action_system(
     interaction_query: Query<(&Interaction, &MenuButtonAction), (Changed<Interaction>, With<Button>)>,
-    mut game_state: ResMut<NextState<AppState>>,
+    mut menu_state: ResMut<NextState<MenuState>>,
+    mut app_exit_events: EventWriter<AppExit>,
+    replay_path: Res<ReplayPath>,
+    mut start_replay_events: EventWriter<StartReplayEvent>,
 ) {
     for (interaction, menu_button_action) in &interaction_query {
         if *interaction == Interaction::Pressed {
             match menu_button_action {
                 MenuButtonAction::Play => {
-                    game_state.set(AppState::GamePlaying);
-                    println!("PLAY BUTTON PRESSED")
+                    menu_state.set(MenuState::CharacterSelect);
                 },
-                _ => {}
+                MenuButtonAction::Shop => {
+                    menu_state.set(MenuState::Shop);
+                },
+                MenuButtonAction::WatchReplay => {
+                    start_replay_events.send(StartReplayEvent(replay_path.0.clone()));
+                },
+                MenuButtonAction::BackToMainMenu => {
+                    menu_state.set(MenuState::MainMenu);
+                },
+                MenuButtonAction::Quit => {
+                    app_exit_events.send(AppExit);
+                }
             }
         }
     }
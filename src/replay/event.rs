@@ -0,0 +1,7 @@
+use std::path::PathBuf;
+
+use bevy::prelude::*;
+
+// Loads the replay at the path and starts playing it back.
+#[derive(Event)]
+pub struct StartReplayEvent(pub PathBuf);
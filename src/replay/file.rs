@@ -0,0 +1,54 @@
+use std::fs;
+use std::path::Path;
+
+use crate::replay::resource::{Replay, REPLAY_VERSION};
+use crate::save::file::SaveError;
+
+// Replays aren't migrated: any change to the simulation makes old recordings play out differently anyway.
+pub fn read_replay(path: &Path) -> Result<Replay, SaveError> {
+    let replay: Replay = ron::from_str(&fs::read_to_string(path)?).map_err(|error| SaveError::Parse(error.to_string()))?;
+
+    if replay.version != REPLAY_VERSION {
+        return Err(SaveError::UnsupportedVersion(replay.version));
+    }
+
+    Ok(replay)
+}
+
+pub fn write_replay(path: &Path, replay: &Replay) -> Result<(), SaveError> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    // Not pretty-printed; a long run is tens of thousands of input spans.
+    let contents = ron::to_string(replay).map_err(|error| SaveError::Parse(error.to_string()))?;
+    fs::write(path, contents)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+    use crate::game::player::resource::{PlayerAction, PlayerInput};
+    use crate::shop::upgrade::ShopUpgrade;
+
+    #[test]
+    fn round_trips_through_disk() {
+        let path = std::env::temp_dir()
+            .join(format!("endless_stream_replay_{}", std::process::id()))
+            .join("replay.ron");
+
+        let mut input = PlayerInput::default();
+        input.set(PlayerAction::MoveUp, true);
+        let mut replay = Replay::new(42, "dorian".to_string(), "default".to_string(), BTreeMap::from([(ShopUpgrade::Damage, 2)]));
+        replay.push(PlayerInput::default());
+        replay.push(input);
+
+        write_replay(&path, &replay).unwrap();
+
+        assert_eq!(read_replay(&path).unwrap(), replay);
+    }
+}
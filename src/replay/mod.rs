@@ -0,0 +1,33 @@
+use bevy::prelude::*;
+
+use crate::AppState;
+use crate::game::player::PlayerInputSet;
+use event::StartReplayEvent;
+use resource::{InputRecorder, ReplayPath, ReplayPlayback};
+use system::*;
+
+pub mod event;
+pub mod file;
+pub mod resource;
+pub mod system;
+
+pub struct ReplayPlugin;
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<StartReplayEvent>()
+            .init_resource::<ReplayPath>()
+            .add_systems(Update, start_replay_system.run_if(in_state(AppState::MainMenu)))
+            .add_systems(OnEnter(AppState::GamePlaying), start_recording.run_if(not(resource_exists::<ReplayPlayback>())))
+            .add_systems(FixedUpdate, (
+                replay_input_system.in_set(PlayerInputSet).run_if(resource_exists::<ReplayPlayback>()),
+                record_input_system.after(PlayerInputSet).run_if(resource_exists::<InputRecorder>()),
+            ).run_if(in_state(AppState::GamePlaying)))
+            .add_systems(Update, replay_controls_system
+                .run_if(in_state(AppState::GamePlaying))
+                .run_if(resource_exists::<ReplayPlayback>()))
+            .add_systems(OnExit(AppState::GamePlaying), finish_recording)
+            .add_systems(OnEnter(AppState::MainMenu), stop_replay)
+            .add_systems(Last, save_recording_on_exit);
+    }
+}
@@ -0,0 +1,171 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::game::camera::resource::ViewExtents;
+use crate::game::player::resource::PlayerInput;
+use crate::shop::upgrade::ShopUpgrade;
+
+pub const REPLAY_VERSION: u32 = 1;
+
+// Everything needed to play a run back: how it was set up, and the player's input on every tick.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Replay {
+    pub version: u32,
+    pub seed: u64,
+    pub character: String,
+    pub level: String,
+    // Shop upgrades change the player's stats, so the replay has to use the ones the run was played with.
+    pub upgrade_ranks: BTreeMap<ShopUpgrade, u32>,
+    // Run-length encoded; the input rarely changes from one tick to the next.
+    pub inputs: Vec<InputSpan>,
+    // Every tick the camera's view changed on, starting with the first; see `ViewExtents`.
+    #[serde(default)]
+    pub views: Vec<ViewChange>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct ViewChange {
+    pub tick: u64,
+    pub extents: ViewExtents,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct InputSpan {
+    pub ticks: u32,
+    pub input: PlayerInput,
+}
+
+impl Replay {
+    pub fn new(seed: u64, character: String, level: String, upgrade_ranks: BTreeMap<ShopUpgrade, u32>) -> Self {
+        Replay {
+            version: REPLAY_VERSION,
+            seed,
+            character,
+            level,
+            upgrade_ranks,
+            inputs: Vec::new(),
+            views: Vec::new(),
+        }
+    }
+
+    pub fn push_view(&mut self, tick: u64, extents: ViewExtents) {
+        if self.views.last().map(|change| change.extents) != Some(extents) {
+            self.views.push(ViewChange { tick, extents });
+        }
+    }
+
+    // Appends one tick of input.
+    pub fn push(&mut self, input: PlayerInput) {
+        match self.inputs.last_mut() {
+            Some(span) if span.input == input && span.ticks < u32::MAX => span.ticks += 1,
+            _ => self.inputs.push(InputSpan { ticks: 1, input }),
+        }
+    }
+
+    pub fn tick_count(&self) -> u64 {
+        self.inputs.iter().map(|span| span.ticks as u64).sum()
+    }
+}
+
+// The replay of the run being played; only present while recording.
+#[derive(Resource)]
+pub struct InputRecorder(pub Replay);
+
+// Present while a run is being played back from a replay instead of the keyboard.
+#[derive(Resource)]
+pub struct ReplayPlayback {
+    pub replay: Replay,
+    span: usize,
+    ticks_into_span: u32,
+    view: usize,
+    pub fast_forward: bool,
+}
+
+impl ReplayPlayback {
+    pub fn new(replay: Replay) -> Self {
+        ReplayPlayback {
+            replay,
+            span: 0,
+            ticks_into_span: 0,
+            view: 0,
+            fast_forward: false,
+        }
+    }
+
+    // The input for the next tick, or `None` once the recording has run out.
+    pub fn next_input(&mut self) -> Option<PlayerInput> {
+        let span = self.replay.inputs.get(self.span)?;
+        let input = span.input;
+
+        self.ticks_into_span += 1;
+        if self.ticks_into_span >= span.ticks {
+            self.span += 1;
+            self.ticks_into_span = 0;
+        }
+
+        Some(input)
+    }
+
+    // The view recorded for `tick`, if it changed on that tick.
+    pub fn next_view(&mut self, tick: u64) -> Option<ViewExtents> {
+        let change = self.replay.views.get(self.view).filter(|change| change.tick <= tick)?;
+        self.view += 1;
+
+        Some(change.extents)
+    }
+}
+
+// Where the most recent run's replay is written, and what the menu's replay button plays.
+#[derive(Resource)]
+pub struct ReplayPath(pub PathBuf);
+
+impl Default for ReplayPath {
+    fn default() -> Self {
+        ReplayPath(PathBuf::from("replays").join("last.ron"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::player::resource::PlayerAction;
+
+    #[test]
+    fn plays_back_what_was_recorded() {
+        let mut moving = PlayerInput::default();
+        moving.set(PlayerAction::MoveLeft, true);
+        let recorded = [PlayerInput::default(), moving, moving, moving, PlayerInput::default()];
+
+        let mut replay = Replay::new(7, "gabe".to_string(), "default".to_string(), BTreeMap::new());
+        for input in recorded {
+            replay.push(input);
+        }
+
+        assert_eq!(replay.inputs.len(), 3);
+        assert_eq!(replay.tick_count(), 5);
+
+        let mut playback = ReplayPlayback::new(replay);
+        let played: Vec<_> = std::iter::from_fn(|| playback.next_input()).collect();
+
+        assert_eq!(played, recorded);
+    }
+
+    #[test]
+    fn plays_back_view_changes_on_the_tick_they_happened() {
+        let zoomed_out = ViewExtents(Vec2::new(640., 360.));
+        let mut replay = Replay::new(7, "gabe".to_string(), "default".to_string(), BTreeMap::new());
+        for (tick, extents) in [(1, ViewExtents::default()), (2, ViewExtents::default()), (3, zoomed_out)] {
+            replay.push_view(tick, extents);
+        }
+
+        assert_eq!(replay.views.len(), 2);
+
+        let mut playback = ReplayPlayback::new(replay);
+        let played: Vec<_> = (1..=4).map(|tick| playback.next_view(tick)).collect();
+
+        assert_eq!(played, [Some(ViewExtents::default()), None, Some(zoomed_out), None]);
+    }
+}
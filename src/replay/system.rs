@@ -0,0 +1,162 @@
+use bevy::app::AppExit;
+use bevy::prelude::*;
+
+use crate::AppState;
+use crate::game::character::SelectedCharacter;
+use crate::data::resource::GameData;
+use crate::game::camera::resource::ViewExtents;
+use crate::game::level::SelectedLevel;
+use crate::game::player::resource::PlayerInput;
+use crate::game::simulation::resource::{RunSeed, SimulationTick};
+use crate::menu::MenuState;
+use crate::replay::event::StartReplayEvent;
+use crate::replay::file::{read_replay, write_replay};
+use crate::replay::resource::{InputRecorder, Replay, ReplayPath, ReplayPlayback};
+use crate::save::resource::SaveData;
+
+const FAST_FORWARD_SPEED: f32 = 4.;
+
+#[allow(clippy::too_many_arguments)]
+pub fn start_replay_system(
+    mut commands: Commands,
+    mut start_events: EventReader<StartReplayEvent>,
+    game_data: Res<GameData>,
+    mut seed: ResMut<RunSeed>,
+    mut selected_character: ResMut<SelectedCharacter>,
+    mut selected_level: ResMut<SelectedLevel>,
+    mut game_state: ResMut<NextState<AppState>>,
+    mut menu_state: ResMut<NextState<MenuState>>,
+) {
+    let Some(StartReplayEvent(path)) = start_events.iter().last() else { return };
+
+    let replay = match read_replay(path) {
+        Ok(replay) => replay,
+        Err(error) => {
+            warn!("Could not load replay {:?}: {}", path, error);
+            return;
+        }
+    };
+
+    if game_data.character(&replay.character).is_none() {
+        warn!("Replay {:?} uses unknown character {}", path, replay.character);
+        return;
+    }
+
+    if game_data.level(&replay.level).is_none() {
+        warn!("Replay {:?} uses unknown level {}", path, replay.level);
+        return;
+    }
+
+    info!("Playing replay {:?} ({} ticks)", path, replay.tick_count());
+    seed.0 = replay.seed;
+    // Replays from before views were recorded were played at the default one.
+    commands.insert_resource(ViewExtents::default());
+    selected_character.0 = replay.character.clone();
+    selected_level.0 = replay.level.clone();
+    commands.insert_resource(ReplayPlayback::new(replay));
+    game_state.set(AppState::GamePlaying);
+    menu_state.set(MenuState::Disabled);
+}
+
+pub fn start_recording(
+    mut commands: Commands,
+    seed: Res<RunSeed>,
+    selected_character: Res<SelectedCharacter>,
+    selected_level: Res<SelectedLevel>,
+    save: Res<SaveData>,
+) {
+    commands.insert_resource(InputRecorder(Replay::new(
+        seed.0,
+        selected_character.0.clone(),
+        selected_level.0.clone(),
+        save.meta.upgrade_ranks.clone(),
+    )));
+}
+
+pub fn record_input_system(
+    player_input: Res<PlayerInput>,
+    view_extents: Res<ViewExtents>,
+    tick: Res<SimulationTick>,
+    mut recorder: ResMut<InputRecorder>,
+) {
+    recorder.0.push(*player_input);
+    recorder.0.push_view(tick.0, *view_extents);
+}
+
+// Feeds the recorded input in place of the keyboard, heading back to the menu once it runs out.
+pub fn replay_input_system(
+    mut playback: ResMut<ReplayPlayback>,
+    tick: Res<SimulationTick>,
+    mut player_input: ResMut<PlayerInput>,
+    mut view_extents: ResMut<ViewExtents>,
+    mut game_state: ResMut<NextState<AppState>>,
+    mut menu_state: ResMut<NextState<MenuState>>,
+) {
+    if let Some(extents) = playback.next_view(tick.0) {
+        *view_extents = extents;
+    }
+
+    match playback.next_input() {
+        Some(input) => *player_input = input,
+        None => {
+            *player_input = PlayerInput::default();
+            game_state.set(AppState::MainMenu);
+            menu_state.set(MenuState::MainMenu);
+        }
+    }
+}
+
+// Space pauses the replay, F toggles fast-forward. Both work by scaling time, so fixed ticks keep their length.
+pub fn replay_controls_system(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut time: ResMut<Time>,
+    mut playback: ResMut<ReplayPlayback>,
+) {
+    if keyboard_input.just_pressed(KeyCode::Space) {
+        if time.is_paused() {
+            time.unpause();
+        } else {
+            time.pause();
+        }
+    }
+
+    if keyboard_input.just_pressed(KeyCode::F) {
+        playback.fast_forward = !playback.fast_forward;
+        time.set_relative_speed(if playback.fast_forward { FAST_FORWARD_SPEED } else { 1. });
+    }
+}
+
+pub fn stop_replay(mut commands: Commands, mut time: ResMut<Time>) {
+    commands.remove_resource::<ReplayPlayback>();
+    time.unpause();
+    time.set_relative_speed(1.);
+}
+
+pub fn finish_recording(mut commands: Commands, recorder: Option<Res<InputRecorder>>, replay_path: Res<ReplayPath>) {
+    let Some(recorder) = recorder else { return };
+
+    save_recording(&recorder, &replay_path);
+    commands.remove_resource::<InputRecorder>();
+}
+
+// Runs aren't ended when the game is closed, so the recording is written on the way out too.
+pub fn save_recording_on_exit(
+    mut exit_events: EventReader<AppExit>,
+    recorder: Option<Res<InputRecorder>>,
+    replay_path: Res<ReplayPath>,
+) {
+    if exit_events.iter().next().is_none() {
+        return;
+    }
+
+    if let Some(recorder) = recorder {
+        save_recording(&recorder, &replay_path);
+    }
+}
+
+fn save_recording(recorder: &InputRecorder, replay_path: &ReplayPath) {
+    match write_replay(&replay_path.0, &recorder.0) {
+        Ok(()) => info!("Saved replay of {} ticks to {:?}", recorder.0.tick_count(), replay_path.0),
+        Err(error) => error!("Failed to write replay: {}", error),
+    }
+}
@@ -0,0 +1,279 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use bevy::prelude::*;
+use ron::ser::PrettyConfig;
+use serde::Deserialize;
+
+use crate::save::resource::SaveData;
+
+pub const SAVE_VERSION: u32 = 1;
+
+// Each entry upgrades a save from version `index + 1` to `index + 2`. They work on the untyped ron value
+// so old layouts never have to be kept around as structs; append one whenever `SAVE_VERSION` is bumped.
+const MIGRATIONS: &[Migration] = &[];
+
+// Bumping the version without adding its migration would otherwise only show up once someone loads an old save.
+const _: () = assert!(MIGRATIONS.len() == SAVE_VERSION as usize - 1);
+
+type Migration = fn(ron::Value) -> Result<ron::Value, SaveError>;
+
+#[derive(Debug)]
+pub enum SaveError {
+    Io(io::Error),
+    Parse(String),
+    UnsupportedVersion(u32),
+}
+
+impl fmt::Display for SaveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SaveError::Io(error) => write!(f, "io error: {}", error),
+            SaveError::Parse(error) => write!(f, "parse error: {}", error),
+            SaveError::UnsupportedVersion(version) => write!(f, "unsupported save version {}", version),
+        }
+    }
+}
+
+impl From<io::Error> for SaveError {
+    fn from(error: io::Error) -> Self {
+        SaveError::Io(error)
+    }
+}
+
+// Only the version is read up front, so migrations can be picked before the rest of the file is trusted.
+#[derive(Deserialize)]
+struct SaveHeader {
+    version: u32,
+}
+
+pub fn parse_save(contents: &str) -> Result<SaveData, SaveError> {
+    parse_save_with(contents, SAVE_VERSION, MIGRATIONS)
+}
+
+fn parse_save_with(contents: &str, current_version: u32, migrations: &[Migration]) -> Result<SaveData, SaveError> {
+    let header: SaveHeader = ron::from_str(contents).map_err(|error| SaveError::Parse(error.to_string()))?;
+
+    if header.version == 0 || header.version > current_version {
+        return Err(SaveError::UnsupportedVersion(header.version));
+    }
+
+    if header.version == current_version {
+        return ron::from_str(contents).map_err(|error| SaveError::Parse(error.to_string()));
+    }
+
+    let mut value: ron::Value = ron::from_str(contents).map_err(|error| SaveError::Parse(error.to_string()))?;
+    let pending = migrations
+        .get((header.version - 1) as usize..(current_version - 1) as usize)
+        .ok_or(SaveError::UnsupportedVersion(header.version))?;
+    for migration in pending {
+        value = migration(value)?;
+    }
+
+    let mut save: SaveData = value.into_rust().map_err(|error| SaveError::Parse(error.to_string()))?;
+    save.version = current_version;
+
+    Ok(save)
+}
+
+// Writes to a temporary file first and keeps the previous save as a backup,
+// so a crash halfway through never leaves the player without a readable save.
+pub fn write_save(path: &Path, save: &SaveData) -> Result<(), SaveError> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let contents = ron::ser::to_string_pretty(save, PrettyConfig::default())
+        .map_err(|error| SaveError::Parse(error.to_string()))?;
+
+    let temp_path = sibling_path(path, "tmp");
+    fs::write(&temp_path, contents)?;
+
+    if path.exists() {
+        fs::copy(path, backup_path(path))?;
+    }
+
+    fs::rename(temp_path, path)?;
+
+    Ok(())
+}
+
+// Loads the save, falling back to the backup and then to a fresh save. Unreadable saves are moved aside
+// instead of being overwritten on the next write, so nothing is lost for good.
+pub fn load_or_recover(path: &Path) -> SaveData {
+    match read_save(path) {
+        Ok(Some(save)) => save,
+        Ok(None) => SaveData::default(),
+        Err(error) => {
+            warn!("Could not load save {:?} ({}); trying backup.", path, error);
+
+            if let Err(error) = fs::rename(path, sibling_path(path, "corrupt")) {
+                warn!("Could not move unreadable save aside: {}", error);
+            }
+
+            match read_save(&backup_path(path)) {
+                Ok(Some(save)) => save,
+                _ => SaveData::default(),
+            }
+        }
+    }
+}
+
+fn read_save(path: &Path) -> Result<Option<SaveData>, SaveError> {
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    parse_save(&fs::read_to_string(path)?).map(Some)
+}
+
+pub fn backup_path(path: &Path) -> PathBuf {
+    sibling_path(path, "bak")
+}
+
+// `save/progress.ron` -> `save/progress.ron.<suffix>`
+fn sibling_path(path: &Path, suffix: &str) -> PathBuf {
+    let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(".");
+    file_name.push(suffix);
+
+    path.with_file_name(file_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::save::resource::RunRecord;
+
+    fn temp_save_path(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("endless_stream_save_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+
+        dir.join("progress.ron")
+    }
+
+    fn sample_save() -> SaveData {
+        let mut save = SaveData::default();
+        save.meta.gold = 420;
+        save.meta.unlocked_weapons.push("whip".to_string());
+        save.record_run(RunRecord {
+            character: "gabe".to_string(),
+            level: "forest".to_string(),
+            duration_secs: 312.5,
+            gold_earned: 57,
+        });
+
+        save
+    }
+
+    #[test]
+    fn round_trips_through_disk() {
+        let path = temp_save_path("round_trip");
+        let save = sample_save();
+
+        write_save(&path, &save).unwrap();
+
+        assert_eq!(load_or_recover(&path), save);
+    }
+
+    #[test]
+    fn missing_save_starts_fresh() {
+        let path = temp_save_path("missing");
+
+        assert_eq!(load_or_recover(&path), SaveData::default());
+    }
+
+    #[test]
+    fn corrupt_save_recovers_from_backup() {
+        let path = temp_save_path("corrupt_backup");
+        let save = sample_save();
+
+        write_save(&path, &save).unwrap();
+        // A second write moves the first save into the backup slot.
+        write_save(&path, &save).unwrap();
+        fs::write(&path, "(version: 1, meta: (gold: ").unwrap();
+
+        assert_eq!(load_or_recover(&path), save);
+        assert!(sibling_path(&path, "corrupt").exists());
+    }
+
+    #[test]
+    fn corrupt_save_without_backup_starts_fresh() {
+        let path = temp_save_path("corrupt_no_backup");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, "definitely not ron").unwrap();
+
+        assert_eq!(load_or_recover(&path), SaveData::default());
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn rejects_saves_from_newer_versions() {
+        let contents = format!("(version: {}, meta: (), run_history: [])", SAVE_VERSION + 1);
+
+        assert!(matches!(parse_save(&contents), Err(SaveError::UnsupportedVersion(_))));
+    }
+
+    // Stands in for a real upgrade until the save layout first changes, so the migration path is exercised against
+    // a save written by version 1.
+    fn unlock_dorian(value: ron::Value) -> Result<ron::Value, SaveError> {
+        let malformed = || SaveError::Parse("unexpected save layout".to_string());
+        let key = |name: &str| ron::Value::String(name.to_string());
+
+        let ron::Value::Map(mut save) = value else { return Err(malformed()) };
+        let Some(ron::Value::Map(mut meta)) = save.remove(&key("meta")) else { return Err(malformed()) };
+        let Some(ron::Value::Seq(mut characters)) = meta.remove(&key("unlocked_characters")) else {
+            return Err(malformed());
+        };
+
+        characters.push(key("dorian"));
+        meta.insert(key("unlocked_characters"), ron::Value::Seq(characters));
+        save.insert(key("meta"), ron::Value::Map(meta));
+
+        Ok(ron::Value::Map(save))
+    }
+
+    #[test]
+    fn migrates_version_one_saves() {
+        let contents = include_str!("../../tests/fixtures/progress_v1.ron");
+
+        let save = parse_save_with(contents, 2, &[unlock_dorian]).unwrap();
+
+        assert_eq!(save.version, 2);
+        assert_eq!(save.meta.unlocked_characters, vec!["gabe".to_string(), "dorian".to_string()]);
+        assert_eq!(save.meta.gold, 420);
+        assert_eq!(save.run_history, sample_save().run_history);
+        assert_eq!(parse_save(contents).unwrap(), sample_save());
+    }
+
+    #[test]
+    fn missing_migrations_are_an_error() {
+        let contents = include_str!("../../tests/fixtures/progress_v1.ron").replace("version: 1", "version: 2");
+
+        assert!(matches!(parse_save_with(&contents, 3, &[unlock_dorian]), Err(SaveError::UnsupportedVersion(2))));
+    }
+
+    #[test]
+    fn missing_meta_fields_use_defaults() {
+        let save = parse_save("(version: 1, meta: (gold: 10), run_history: [])").unwrap();
+
+        assert_eq!(save.meta.gold, 10);
+        assert_eq!(save.meta.unlocked_characters, vec!["gabe".to_string()]);
+    }
+
+    #[test]
+    fn best_time_only_improves() {
+        let mut save = sample_save();
+        save.record_run(RunRecord {
+            character: "gabe".to_string(),
+            level: "forest".to_string(),
+            duration_secs: 10.,
+            gold_earned: 0,
+        });
+
+        assert_eq!(save.meta.best_times["forest"], 312.5);
+        assert_eq!(save.run_history.len(), 2);
+    }
+}
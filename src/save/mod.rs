@@ -0,0 +1,65 @@
+use bevy::prelude::*;
+
+use crate::AppState;
+use crate::game::character::SelectedCharacter;
+use crate::game::level::SelectedLevel;
+use crate::replay::resource::ReplayPlayback;
+use file::{load_or_recover, write_save};
+use resource::{CurrentRun, RunRecord, SaveData, SavePath};
+
+pub mod file;
+pub mod resource;
+
+pub struct SavePlugin;
+
+impl Plugin for SavePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SavePath>()
+            .init_resource::<SaveData>()
+            .init_resource::<CurrentRun>()
+            .add_systems(Startup, load_save)
+            .add_systems(OnEnter(AppState::GamePlaying), start_run)
+            .add_systems(FixedUpdate, run_timer_system.run_if(in_state(AppState::GamePlaying)))
+            .add_systems(OnExit(AppState::GamePlaying), finish_run);
+    }
+}
+
+fn load_save(mut commands: Commands, save_path: Res<SavePath>) {
+    commands.insert_resource(load_or_recover(&save_path.0));
+}
+
+fn start_run(mut current_run: ResMut<CurrentRun>, selected_character: Res<SelectedCharacter>, selected_level: Res<SelectedLevel>) {
+    *current_run = CurrentRun {
+        character: selected_character.0.clone(),
+        level: selected_level.0.clone(),
+        ..default()
+    };
+}
+
+fn run_timer_system(fixed_time: Res<FixedTime>, mut current_run: ResMut<CurrentRun>) {
+    current_run.elapsed_secs += fixed_time.period.as_secs_f32();
+}
+
+fn finish_run(
+    current_run: Res<CurrentRun>,
+    save_path: Res<SavePath>,
+    mut save: ResMut<SaveData>,
+    playback: Option<Res<ReplayPlayback>>,
+) {
+    // Watching a replay doesn't earn anything.
+    if playback.is_some() {
+        return;
+    }
+
+    save.meta.gold += current_run.gold_earned;
+    save.record_run(RunRecord {
+        character: current_run.character.clone(),
+        level: current_run.level.clone(),
+        duration_secs: current_run.elapsed_secs,
+        gold_earned: current_run.gold_earned,
+    });
+
+    if let Err(error) = write_save(&save_path.0, &save) {
+        error!("Failed to write save: {}", error);
+    }
+}
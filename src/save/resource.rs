@@ -0,0 +1,94 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::save::file::SAVE_VERSION;
+use crate::shop::upgrade::ShopUpgrade;
+
+// Everything that survives between sessions; loaded once on startup and written back after each run.
+#[derive(Resource, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SaveData {
+    pub version: u32,
+    pub meta: MetaProgression,
+    pub run_history: Vec<RunRecord>,
+}
+
+impl Default for SaveData {
+    fn default() -> Self {
+        SaveData {
+            version: SAVE_VERSION,
+            meta: MetaProgression::default(),
+            run_history: Vec::new(),
+        }
+    }
+}
+
+impl SaveData {
+    // Adds a finished run to the history, keeping the per-level best time up to date.
+    pub fn record_run(&mut self, run: RunRecord) {
+        let best = self.meta.best_times.entry(run.level.clone()).or_insert(0.);
+        if run.duration_secs > *best {
+            *best = run.duration_secs;
+        }
+
+        self.run_history.push(run);
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct MetaProgression {
+    pub unlocked_characters: Vec<String>,
+    pub unlocked_weapons: Vec<String>,
+    pub gold: u32,
+    // Longest survived time in seconds, keyed by level name.
+    pub best_times: BTreeMap<String, f32>,
+    pub upgrade_ranks: BTreeMap<ShopUpgrade, u32>,
+}
+
+impl MetaProgression {
+    pub fn upgrade_rank(&self, upgrade: ShopUpgrade) -> u32 {
+        self.upgrade_ranks.get(&upgrade).copied().unwrap_or(0)
+    }
+}
+
+impl Default for MetaProgression {
+    fn default() -> Self {
+        MetaProgression {
+            unlocked_characters: vec!["gabe".to_string()],
+            unlocked_weapons: Vec::new(),
+            gold: 0,
+            best_times: BTreeMap::new(),
+            upgrade_ranks: BTreeMap::new(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RunRecord {
+    pub character: String,
+    pub level: String,
+    pub duration_secs: f32,
+    pub gold_earned: u32,
+}
+
+// Where the save file lives; kept as a resource so tests and tools can point it elsewhere.
+#[derive(Resource)]
+pub struct SavePath(pub PathBuf);
+
+impl Default for SavePath {
+    fn default() -> Self {
+        SavePath(PathBuf::from("save").join("progress.ron"))
+    }
+}
+
+// Tracks the run currently being played, turned into a `RunRecord` once the run ends.
+#[derive(Resource, Default)]
+pub struct CurrentRun {
+    pub character: String,
+    pub level: String,
+    pub elapsed_secs: f32,
+    pub gold_earned: u32,
+}
@@ -0,0 +1,40 @@
+use bevy::prelude::*;
+
+// Player facing settings; systems that depend on these should react to `Res<Settings>::is_changed()`
+// rather than reading them once on setup.
+#[derive(Resource)]
+pub struct Settings {
+    // Orthographic scale of the game camera; lower values zoom in.
+    pub camera_zoom: f32,
+    pub screen_shake: bool,
+    pub damage_numbers: bool,
+    pub hit_flash: bool,
+    // Volumes between 0 and 1; music and sound effects are both scaled by the master volume.
+    pub master_volume: f32,
+    pub music_volume: f32,
+    pub sfx_volume: f32,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            camera_zoom: 0.5,
+            screen_shake: true,
+            damage_numbers: true,
+            hit_flash: true,
+            master_volume: 1.,
+            music_volume: 0.6,
+            sfx_volume: 0.8,
+        }
+    }
+}
+
+impl Settings {
+    pub fn music_volume(&self) -> f32 {
+        (self.master_volume * self.music_volume).clamp(0., 1.)
+    }
+
+    pub fn sfx_volume(&self) -> f32 {
+        (self.master_volume * self.sfx_volume).clamp(0., 1.)
+    }
+}
@@ -0,0 +1,13 @@
+use bevy::prelude::*;
+
+use crate::shop::upgrade::ShopUpgrade;
+
+#[derive(Component)]
+pub struct ShopButtonAction(pub ShopUpgrade);
+
+#[derive(Component)]
+pub struct ShopGoldText;
+
+// Label of an upgrade button, refreshed whenever the save changes.
+#[derive(Component)]
+pub struct ShopUpgradeText(pub ShopUpgrade);
@@ -0,0 +1,66 @@
+use bevy::prelude::*;
+
+use crate::AppState;
+use crate::menu::{spawn_menu_button, MenuState};
+use crate::menu::component::MenuButtonAction;
+use component::{ShopButtonAction, ShopGoldText, ShopUpgradeText};
+use resource::ShopScreen;
+use system::*;
+use upgrade::ShopUpgrade;
+
+pub mod component;
+pub mod resource;
+pub mod system;
+pub mod upgrade;
+
+pub struct ShopPlugin;
+
+impl Plugin for ShopPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(MenuState::Shop), setup_shop)
+            .add_systems(Update, (shop_purchase_system, shop_text_system).chain()
+                .run_if(in_state(AppState::MainMenu).and_then(in_state(MenuState::Shop))))
+            .add_systems(OnExit(MenuState::Shop), cleanup_shop);
+    }
+}
+
+fn setup_shop(mut commands: Commands) {
+    let root = commands
+        .spawn(NodeBundle {
+            style: Style {
+                width: Val::Percent(100.),
+                height: Val::Percent(100.),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            ..default()
+        }).with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font_size: 40.,
+                        color: Color::rgb(1., 0.84, 0.),
+                        ..default()
+                    },
+                ),
+                ShopGoldText,
+            ));
+
+            for upgrade in ShopUpgrade::ALL {
+                // The label is filled in by `shop_text_system`, which also keeps it up to date after purchases.
+                spawn_menu_button(parent, "", (ShopButtonAction(upgrade), ShopUpgradeText(upgrade)));
+            }
+
+            spawn_menu_button(parent, "Back", MenuButtonAction::BackToMainMenu);
+        })
+        .id();
+
+    commands.insert_resource(ShopScreen { root });
+}
+
+fn cleanup_shop(mut commands: Commands, shop_screen: Res<ShopScreen>) {
+    commands.entity(shop_screen.root).despawn_recursive();
+}
@@ -0,0 +1,6 @@
+use bevy::prelude::*;
+
+#[derive(Resource)]
+pub struct ShopScreen {
+    pub root: Entity,
+}
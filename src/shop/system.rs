@@ -0,0 +1,65 @@
+use bevy::prelude::*;
+
+use crate::data::resource::GameData;
+use crate::save::file::write_save;
+use crate::save::resource::{SaveData, SavePath};
+use crate::shop::component::{ShopButtonAction, ShopGoldText, ShopUpgradeText};
+
+#[allow(clippy::type_complexity)]
+pub fn shop_purchase_system(
+    interaction_query: Query<(&Interaction, &ShopButtonAction), (Changed<Interaction>, With<Button>)>,
+    save_path: Res<SavePath>,
+    game_data: Res<GameData>,
+    mut save: ResMut<SaveData>,
+) {
+    for (interaction, ShopButtonAction(upgrade)) in &interaction_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        let rank = save.meta.upgrade_rank(*upgrade);
+        let Some(cost) = game_data.upgrade(*upgrade).cost(rank) else { continue };
+        if save.meta.gold < cost {
+            continue;
+        }
+
+        save.meta.gold -= cost;
+        save.meta.upgrade_ranks.insert(*upgrade, rank + 1);
+
+        if let Err(error) = write_save(&save_path.0, &save) {
+            error!("Failed to write save after purchase: {}", error);
+        }
+    }
+}
+
+pub fn shop_text_system(
+    save: Res<SaveData>,
+    game_data: Res<GameData>,
+    mut gold_text: Query<&mut Text, With<ShopGoldText>>,
+    upgrade_buttons: Query<(&ShopUpgradeText, &Children)>,
+    mut texts: Query<&mut Text, Without<ShopGoldText>>,
+    added_buttons: Query<(), Added<ShopUpgradeText>>,
+) {
+    if !save.is_changed() && !game_data.is_changed() && added_buttons.is_empty() {
+        return;
+    }
+
+    for mut text in &mut gold_text {
+        text.sections[0].value = format!("Gold: {}", save.meta.gold);
+    }
+
+    for (ShopUpgradeText(upgrade), children) in &upgrade_buttons {
+        let rank = save.meta.upgrade_rank(*upgrade);
+        let definition = game_data.upgrade(*upgrade);
+        let label = match definition.cost(rank) {
+            Some(cost) => format!("{} {}/{} - {}g", upgrade.name(), rank, definition.max_rank, cost),
+            None => format!("{} MAX", upgrade.name()),
+        };
+
+        for child in children.iter() {
+            if let Ok(mut text) = texts.get_mut(*child) {
+                text.sections[0].value = label.clone();
+            }
+        }
+    }
+}
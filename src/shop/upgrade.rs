@@ -0,0 +1,71 @@
+use serde::{Deserialize, Serialize};
+
+use crate::game::stats::component::Stat;
+
+// Permanent stat bonuses bought with gold between runs.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum ShopUpgrade {
+    MaxHealth,
+    MoveSpeed,
+    PickupRadius,
+    Damage,
+}
+
+impl ShopUpgrade {
+    pub const ALL: [ShopUpgrade; 4] = [
+        ShopUpgrade::MaxHealth,
+        ShopUpgrade::MoveSpeed,
+        ShopUpgrade::PickupRadius,
+        ShopUpgrade::Damage,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ShopUpgrade::MaxHealth => "Max Health",
+            ShopUpgrade::MoveSpeed => "Move Speed",
+            ShopUpgrade::PickupRadius => "Pickup Radius",
+            ShopUpgrade::Damage => "Damage",
+        }
+    }
+
+    pub fn stat(&self) -> Stat {
+        match self {
+            ShopUpgrade::MaxHealth => Stat::MaxHealth,
+            ShopUpgrade::MoveSpeed => Stat::MoveSpeed,
+            ShopUpgrade::PickupRadius => Stat::PickupRadius,
+            ShopUpgrade::Damage => Stat::Damage,
+        }
+    }
+}
+
+// Defined in `assets/data/game.upgrades.ron`.
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct UpgradeDefinition {
+    pub max_rank: u32,
+    pub base_cost: u32,
+    // Fractional bonus per rank owned; e.g. 0.1 is +10%.
+    pub bonus_per_rank: f32,
+}
+
+impl UpgradeDefinition {
+    // Cost of buying the rank after `current_rank`, or `None` once maxed out.
+    pub fn cost(&self, current_rank: u32) -> Option<u32> {
+        if current_rank >= self.max_rank {
+            return None;
+        }
+
+        Some(self.base_cost * (current_rank + 1))
+    }
+
+    pub fn bonus(&self, rank: u32) -> f32 {
+        self.bonus_per_rank * rank.min(self.max_rank) as f32
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.max_rank == 0 || self.bonus_per_rank < 0. {
+            return Err("upgrades need at least one rank and a non-negative bonus".to_string());
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,74 @@
+use bevy::prelude::*;
+
+use crate::AppState;
+
+const SPLASH_SECS: f32 = 2.;
+
+#[derive(Resource)]
+struct Splash {
+    root: Entity,
+    camera: Entity,
+    timer: Timer,
+}
+
+// Shows the title for a moment on startup, then moves on to loading. Any key or click skips it.
+pub struct SplashPlugin;
+
+impl Plugin for SplashPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(AppState::SplashScreen), setup_splash)
+            .add_systems(Update, splash_system.run_if(in_state(AppState::SplashScreen)))
+            .add_systems(OnExit(AppState::SplashScreen), cleanup_splash);
+    }
+}
+
+fn setup_splash(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let camera = commands.spawn(Camera2dBundle::default()).id();
+    let root = commands
+        .spawn(NodeBundle {
+            style: Style {
+                width: Val::Percent(100.),
+                height: Val::Percent(100.),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            ..default()
+        }).with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                "endless-stream",
+                TextStyle {
+                    font: asset_server.load("font/Golden Age.ttf"),
+                    font_size: 80.,
+                    color: Color::WHITE,
+                },
+            ));
+        })
+        .id();
+
+    commands.insert_resource(Splash {
+        root,
+        camera,
+        timer: Timer::from_seconds(SPLASH_SECS, TimerMode::Once),
+    });
+}
+
+fn splash_system(
+    time: Res<Time>,
+    keyboard_input: Res<Input<KeyCode>>,
+    mouse_input: Res<Input<MouseButton>>,
+    mut splash: ResMut<Splash>,
+    mut game_state: ResMut<NextState<AppState>>,
+) {
+    let skipped = keyboard_input.get_just_pressed().next().is_some() || mouse_input.get_just_pressed().next().is_some();
+
+    if splash.timer.tick(time.delta()).finished() || skipped {
+        game_state.set(AppState::Loading);
+    }
+}
+
+fn cleanup_splash(mut commands: Commands, splash: Res<Splash>) {
+    commands.entity(splash.root).despawn_recursive();
+    commands.entity(splash.camera).despawn_recursive();
+    commands.remove_resource::<Splash>();
+}
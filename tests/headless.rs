@@ -0,0 +1,315 @@
+use bevy::prelude::*;
+use endless_stream::AppState;
+use endless_stream::data::asset::{EnemyData, LevelData};
+use endless_stream::data::resource::{DataFiles, GameData};
+use endless_stream::game::enemy::component::{Enemy, EnemyKind};
+use endless_stream::game::health::component::Health;
+use endless_stream::game::health::event::DamageEvent;
+use endless_stream::game::movement::component::{Collidable, KnockbackResistance};
+use endless_stream::game::pickup::component::{EvolutionOffer, Pickup};
+use endless_stream::game::prop::component::PropKind;
+use endless_stream::game::stats::component::{ModifierSource, Stat, Stats};
+use endless_stream::game::status::component::{StatusEffectKind, StatusEffects};
+use endless_stream::game::weapon::component::{Weapon, WeaponKind};
+use endless_stream::harness::HeadlessHarness;
+
+// The level floor is 5000x2000 around the origin and the player's collider is 26x20.
+const LEVEL_BOTTOM: f32 = -1000.;
+const PLAYER_HALF_HEIGHT: f32 = 10.;
+
+#[test]
+fn starting_a_run_spawns_the_player() {
+    let mut harness = HeadlessHarness::new();
+    assert_eq!(harness.state(), AppState::MainMenu);
+
+    harness.start_run("dorian", 1);
+
+    assert_eq!(harness.state(), AppState::GamePlaying);
+    assert_eq!(harness.player_health(), 150.);
+    assert_eq!(harness.player_translation().truncate(), Vec2::ZERO);
+}
+
+#[test]
+fn player_cannot_leave_level_floor() {
+    let mut harness = HeadlessHarness::new();
+    harness.start_run("gabe", 1);
+
+    // Walking straight down from the spawn point; nothing stands in the way but the edge of the level.
+    harness.press(KeyCode::S).advance_ticks(600);
+    let y = harness.player_translation().y;
+
+    assert!(y >= LEVEL_BOTTOM + PLAYER_HALF_HEIGHT, "player left the level at y = {}", y);
+    assert!(y < LEVEL_BOTTOM + PLAYER_HALF_HEIGHT + 5., "player stopped early at y = {}", y);
+}
+
+#[test]
+fn player_collides_with_tree() {
+    let mut harness = HeadlessHarness::new();
+    harness.start_run("gabe", 1);
+
+    // There's a tree (with a 16x20 collider) at (0, 500), right above the spawn point.
+    harness.press(KeyCode::W).advance_ticks(300);
+    let translation = harness.player_translation();
+
+    assert!(translation.y <= 500. - 10. - PLAYER_HALF_HEIGHT, "player walked through the tree to y = {}", translation.y);
+    assert!(translation.y > 450., "player stopped early at y = {}", translation.y);
+    assert_eq!(translation.x, 0.);
+}
+
+#[test]
+fn identical_seeds_and_inputs_play_out_identically() {
+    let play = || {
+        let mut harness = HeadlessHarness::new();
+        harness.start_run("gabe", 42);
+        harness.press(KeyCode::D).advance_ticks(90);
+        harness.release(KeyCode::D).press(KeyCode::W).advance_ticks(90);
+
+        (harness.player_translation(), harness.player_health(), harness.translations::<Enemy>())
+    };
+
+    assert_eq!(play(), play());
+}
+
+// Edits the loaded enemy data in place, which is what the asset watcher does when the file changes on disk.
+fn edit_enemy_data(harness: &mut HeadlessHarness, edit: impl FnOnce(&mut EnemyData)) {
+    let handle = harness.app.world.resource::<DataFiles>().enemies.clone();
+    let mut enemy_data = harness.app.world.resource_mut::<Assets<EnemyData>>();
+    edit(enemy_data.get_mut(&handle).expect("enemy data never loaded"));
+
+    // One frame to send the change event and one to react to it.
+    harness.advance_ticks(2);
+}
+
+#[test]
+fn edited_enemy_data_applies_to_living_enemies() {
+    let mut harness = HeadlessHarness::new();
+    harness.start_run("gabe", 1);
+
+    edit_enemy_data(&mut harness, |enemy_data| {
+        for archetype in &mut enemy_data.0 {
+            archetype.knockback_resistance = 1.;
+        }
+    });
+
+    let mut resistances = harness.app.world.query_filtered::<&KnockbackResistance, With<Enemy>>();
+    assert!(resistances.iter(&harness.app.world).all(|resistance| resistance.0 == 1.));
+}
+
+#[test]
+fn invalid_enemy_data_is_ignored() {
+    let mut harness = HeadlessHarness::new();
+    harness.start_run("gabe", 1);
+    let before = harness.app.world.resource::<GameData>().clone();
+
+    edit_enemy_data(&mut harness, |enemy_data| enemy_data.0[0].max_health = -1.);
+
+    assert_eq!(*harness.app.world.resource::<GameData>(), before);
+}
+
+#[test]
+fn edits_spanning_data_files_apply_once_every_file_is_saved() {
+    let mut harness = HeadlessHarness::new();
+    let files = harness.app.world.resource::<DataFiles>();
+    let (enemies, levels) = (files.enemies.clone(), files.levels.clone());
+
+    // The level names its new boss before the enemy file defines it, which on its own is invalid.
+    let mut level_data = harness.app.world.resource_mut::<Assets<LevelData>>();
+    level_data.get_mut(&levels).expect("level data never loaded").0[0].boss.as_mut().unwrap().archetype = "wraith".to_string();
+    // No run is going yet, so frames rather than ticks: one to send the change event and one to react to it.
+    harness.app.update();
+    harness.app.update();
+    assert_eq!(harness.app.world.resource::<GameData>().levels[0].boss.as_ref().unwrap().archetype, "ogre");
+
+    let mut enemy_data = harness.app.world.resource_mut::<Assets<EnemyData>>();
+    let enemy_data = enemy_data.get_mut(&enemies).expect("enemy data never loaded");
+    let mut wraith = enemy_data.0.iter().find(|archetype| archetype.id == "ogre").unwrap().clone();
+    wraith.id = "wraith".to_string();
+    enemy_data.0.push(wraith);
+    harness.app.update();
+    harness.app.update();
+
+    let game_data = harness.app.world.resource::<GameData>();
+    assert_eq!(game_data.levels[0].boss.as_ref().unwrap().archetype, "wraith");
+    assert!(game_data.archetype("wraith").is_some());
+}
+
+#[test]
+fn broken_props_stop_blocking_drop_loot_and_disappear() {
+    let mut harness = HeadlessHarness::new();
+    harness.start_run("gabe", 1);
+
+    // Braziers always drop something.
+    let mut props = harness.app.world.query::<(Entity, &PropKind)>();
+    let (brazier, _) = props.iter(&harness.app.world).find(|(_, kind)| kind.0 == "brazier").expect("no brazier in the level");
+    let brazier_position = harness.app.world.get::<Transform>(brazier).unwrap().translation.truncate();
+
+    harness.app.world.send_event(DamageEvent { target: brazier, amount: 1000. });
+    harness.advance_ticks(1);
+
+    assert!(harness.app.world.get::<Collidable>(brazier).is_none());
+    let pickups = harness.translations::<Pickup>();
+    assert!(pickups.iter().any(|(_, translation)| translation.truncate().distance(brazier_position) < 16.), "no loot near the brazier");
+
+    harness.advance_ticks(60);
+    assert!(harness.app.world.get_entity(brazier).is_none());
+}
+
+#[test]
+fn freeze_slows_every_enemy() {
+    let mut harness = HeadlessHarness::new();
+    harness.start_run("gabe", 1);
+
+    harness.app.world.spawn((Transform::default(), Pickup::Freeze(5.)));
+    harness.advance_ticks(1);
+
+    let mut enemies = harness.app.world.query_filtered::<Option<&StatusEffects>, With<Enemy>>();
+    let slowed = |status_effects: Option<&StatusEffects>| {
+        status_effects.is_some_and(|status_effects| status_effects.effects.iter().any(|effect| effect.kind == StatusEffectKind::Slow))
+    };
+    assert!(enemies.iter(&harness.app.world).all(slowed));
+}
+
+#[test]
+fn the_level_boss_shows_up_on_time_around_the_player() {
+    let mut harness = HeadlessHarness::new();
+    let handle = harness.app.world.resource::<DataFiles>().levels.clone();
+    let mut level_data = harness.app.world.resource_mut::<Assets<LevelData>>();
+    let boss = level_data.get_mut(&handle).expect("level data never loaded").0[0].boss.as_mut().unwrap();
+    boss.after_secs = 1.;
+    boss.distance = 300.;
+    // No run is going yet, so frames rather than ticks: one to send the change event and one to react to it.
+    harness.app.update();
+    harness.app.update();
+    harness.start_run("gabe", 1);
+
+    let bosses = |harness: &mut HeadlessHarness| {
+        let mut enemies = harness.app.world.query_filtered::<(&EnemyKind, &Transform), With<Enemy>>();
+        enemies.iter(&harness.app.world)
+            .filter(|(kind, _)| kind.0 == "ogre")
+            .map(|(_, transform)| transform.translation.truncate())
+            .collect::<Vec<_>>()
+    };
+
+    // Starting the run already ran the first of the 60 ticks in a second.
+    harness.advance_ticks(58);
+    assert!(bosses(&mut harness).is_empty());
+
+    harness.advance_ticks(1);
+    let bosses = bosses(&mut harness);
+    assert_eq!(bosses.len(), 1);
+    assert!((bosses[0].length() - 300.).abs() < 20., "boss spawned at {}", bosses[0]);
+}
+
+// Drops a chest on the player, returning the kind and rank of their weapon once it's been opened.
+fn open_chest(harness: &mut HeadlessHarness, chest: Pickup) -> (WeaponKind, u32) {
+    harness.app.world.spawn((Transform::default(), chest));
+    harness.advance_ticks(1);
+
+    weapon(harness)
+}
+
+fn weapon(harness: &mut HeadlessHarness) -> (WeaponKind, u32) {
+    let mut weapons = harness.app.world.query::<&Weapon>();
+    let weapon = weapons.single(&harness.app.world);
+
+    (weapon.kind, weapon.rank)
+}
+
+// Dorian starts with an axe and regeneration, the axe's evolution recipe; this maxes the axe out.
+fn max_out_dorians_axe() -> HeadlessHarness {
+    let mut harness = HeadlessHarness::new();
+    harness.start_run("dorian", 1);
+    let max_rank = harness.app.world.resource::<GameData>().weapon(WeaponKind::Axe).max_rank;
+
+    for rank in 2..=max_rank {
+        assert_eq!(open_chest(&mut harness, Pickup::Chest), (WeaponKind::Axe, rank));
+    }
+
+    harness
+}
+
+fn evolution_offer(harness: &mut HeadlessHarness) -> Option<EvolutionOffer> {
+    let player = harness.player();
+    harness.app.world.get::<EvolutionOffer>(player).copied()
+}
+
+#[test]
+fn regular_chests_never_evolve_weapons() {
+    let mut harness = max_out_dorians_axe();
+    let max_rank = harness.app.world.resource::<GameData>().weapon(WeaponKind::Axe).max_rank;
+
+    assert_eq!(open_chest(&mut harness, Pickup::Chest), (WeaponKind::Axe, max_rank));
+    assert_eq!(evolution_offer(&mut harness), None);
+}
+
+#[test]
+fn boss_chests_offer_evolutions_that_take_effect_once_accepted() {
+    let mut harness = max_out_dorians_axe();
+    let max_rank = harness.app.world.resource::<GameData>().weapon(WeaponKind::Axe).max_rank;
+
+    // Nothing changes until the player answers.
+    assert_eq!(open_chest(&mut harness, Pickup::BossChest), (WeaponKind::Axe, max_rank));
+    assert_eq!(evolution_offer(&mut harness).map(|offer| offer.into), Some(WeaponKind::Cleaver));
+    harness.advance_ticks(30);
+    assert_eq!(weapon(&mut harness), (WeaponKind::Axe, max_rank));
+
+    harness.press(KeyCode::E).advance_ticks(1);
+    assert_eq!(weapon(&mut harness), (WeaponKind::Cleaver, 1));
+    assert_eq!(evolution_offer(&mut harness), None);
+}
+
+#[test]
+fn declined_boss_chests_open_like_regular_ones() {
+    let mut harness = max_out_dorians_axe();
+    let max_rank = harness.app.world.resource::<GameData>().weapon(WeaponKind::Axe).max_rank;
+
+    open_chest(&mut harness, Pickup::BossChest);
+    harness.press(KeyCode::Q).advance_ticks(1);
+
+    assert_eq!(weapon(&mut harness), (WeaponKind::Axe, max_rank));
+    assert_eq!(evolution_offer(&mut harness), None);
+    let player = harness.player();
+    let stats = harness.app.world.get::<Stats>(player).unwrap();
+    assert_eq!(stats.modifiers().iter().filter(|modifier| modifier.source == ModifierSource::Chest).count(), 1);
+}
+
+#[test]
+fn chests_grant_stat_upgrades_once_weapons_are_maxed_without_their_passive() {
+    // Gabe's knife needs might, which he doesn't have.
+    let mut harness = HeadlessHarness::new();
+    harness.start_run("gabe", 1);
+    let max_rank = harness.app.world.resource::<GameData>().weapon(WeaponKind::Knife).max_rank;
+
+    for _ in 2..=max_rank {
+        open_chest(&mut harness, Pickup::Chest);
+    }
+
+    // With nothing to offer, even a boss chest opens like a regular one.
+    assert_eq!(open_chest(&mut harness, Pickup::BossChest), (WeaponKind::Knife, max_rank));
+    let player = harness.player();
+    let stats = harness.app.world.get::<Stats>(player).unwrap();
+    assert_eq!(stats.modifiers().iter().filter(|modifier| modifier.source == ModifierSource::Chest).count(), 1);
+}
+
+#[test]
+fn every_weapon_behavior_damages_a_nearby_enemy() {
+    for kind in WeaponKind::ALL {
+        let mut harness = HeadlessHarness::new();
+        harness.start_run("gabe", 1);
+
+        let stats = harness.app.world.resource::<GameData>().weapon(kind).clone();
+        let mut weapons = harness.app.world.query::<&mut Weapon>();
+        *weapons.single_mut(&mut harness.app.world) = Weapon::new(kind, &stats);
+
+        // An enemy standing still just east of the player, the way they face at the start of a run.
+        let (target, _) = harness.translations::<Enemy>()[0];
+        let mut enemy = harness.app.world.entity_mut(target);
+        enemy.get_mut::<Transform>().unwrap().translation = Vec3::new(60., 0., 0.);
+        enemy.get_mut::<Stats>().unwrap().set_base(Stat::MoveSpeed, 0.);
+
+        harness.advance_ticks((stats.cooldown_secs * 60.) as u64 + 60);
+
+        let health = harness.app.world.get::<Health>(target);
+        assert!(health.is_none_or(|health| health.current < health.max), "{} never hit the enemy", kind.name());
+    }
+}